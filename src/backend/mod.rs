@@ -0,0 +1,129 @@
+/// The source of the tiles seen by the scanner, separating the scan logic from the
+/// `robotics_lib` interfaces.
+pub mod discovery_backend {
+    use crate::coordinates::map_coordinate::MapCoordinate;
+    use crate::tool::resource_scanner::ResourceScanner;
+    use robotics_lib::interface::{robot_map, robot_view};
+    use robotics_lib::runner::Runnable;
+    use robotics_lib::utils::LibError;
+    use robotics_lib::world::tile::Tile;
+    use robotics_lib::world::World;
+    use std::collections::HashMap;
+
+    /// The `DiscoveryBackend` trait abstracts over the operations a scan performs on the world.
+    ///
+    /// The scanner uses an implementation backed by `discover_tiles`, `robot_view` and
+    /// `robot_map` when it runs inside a game. With the `test_utils` feature an in-memory
+    /// implementation is available as well, to test the scan logic without a `Runner`.
+    ///
+    /// Coordinates follow the `MapCoordinate` convention: the filled maps are keyed by `(x, y)`,
+    /// while `known_map` is indexed `[row][col]` like `robot_map`.
+    pub trait DiscoveryBackend {
+        /// Discovers the tiles at the given coordinates, inserting them into `tiles` keyed by
+        /// `(x, y)`.
+        ///
+        /// The map is filled instead of returned, so that a discovery made of several calls
+        /// collects its tiles in a single map.
+        ///
+        /// # Errors
+        ///
+        /// Returns a `LibError` if the tiles cannot be discovered, e.g. for lack of energy.
+        fn discover(
+            &mut self,
+            coordinates: &[MapCoordinate],
+            tiles: &mut HashMap<(usize, usize), Option<Tile>>,
+        ) -> Result<(), LibError>;
+
+        /// Reveals the given coordinates, all lying in the 3x3 view around the robot, the way
+        /// `robot_view` does. Defaults to [`DiscoveryBackend::discover`].
+        fn view(
+            &mut self,
+            coordinates: &[MapCoordinate],
+            tiles: &mut HashMap<(usize, usize), Option<Tile>>,
+        ) -> Result<(), LibError> {
+            self.discover(coordinates, tiles)
+        }
+
+        /// Returns the map of the tiles known to the robot, indexed `[row][col]`, or `None` if it
+        /// cannot be read.
+        fn known_map(&self) -> Option<Vec<Vec<Option<Tile>>>>;
+
+        /// Returns the position of the robot.
+        fn position(&self) -> MapCoordinate;
+
+        /// Returns the energy level of the robot.
+        fn energy_level(&self) -> usize;
+    }
+
+    /// The `RobotBackend` struct serves a scan through the `robotics_lib` interfaces, on behalf
+    /// of a robot in its world.
+    ///
+    /// The `(row, col)` keys handed to `discover_tiles` are written into a buffer lent by the
+    /// scanner, and given back by [`RobotBackend::into_keys`], so that they are not allocated for
+    /// each discovery.
+    pub(crate) struct RobotBackend<'a, R: Runnable> {
+        world: &'a mut World,
+        robot: &'a mut R,
+        keys: Vec<(usize, usize)>,
+    }
+
+    impl<'a, R: Runnable> RobotBackend<'a, R> {
+        /// Creates a new `RobotBackend` for the given robot and world, with the buffer of the keys.
+        pub(crate) fn new(world: &'a mut World, robot: &'a mut R, keys: Vec<(usize, usize)>) -> Self {
+            Self { world, robot, keys }
+        }
+
+        /// Returns the buffer of the keys, to be lent to the next backend.
+        pub(crate) fn into_keys(self) -> Vec<(usize, usize)> {
+            self.keys
+        }
+    }
+
+    impl<'a, R: Runnable> DiscoveryBackend for RobotBackend<'a, R> {
+        fn discover(
+            &mut self,
+            coordinates: &[MapCoordinate],
+            tiles: &mut HashMap<(usize, usize), Option<Tile>>,
+        ) -> Result<(), LibError> {
+            ResourceScanner::discover_coordinates(self.world, self.robot, &mut self.keys, coordinates, tiles)
+        }
+
+        fn view(
+            &mut self,
+            coordinates: &[MapCoordinate],
+            tiles: &mut HashMap<(usize, usize), Option<Tile>>,
+        ) -> Result<(), LibError> {
+            let tilemap = robot_view(self.robot, self.world);
+            let position = self.position();
+            // convert the robot_view output to the discover_tiles output, covering the given
+            // coordinates only (tiles not returned are unknown)
+            for coordinate in coordinates.iter() {
+                let x = coordinate.x();
+                let y = coordinate.y();
+                // compute the tile coordinates in the view FoR from the world FoR
+                let tile = tilemap
+                    .get(y + 1 - position.y())
+                    .and_then(|row| row.get(x + 1 - position.x()))
+                    .cloned()
+                    .flatten();
+                tiles.insert((x, y), tile);
+            }
+            Ok(())
+        }
+
+        fn known_map(&self) -> Option<Vec<Vec<Option<Tile>>>> {
+            robot_map(self.world)
+        }
+
+        fn position(&self) -> MapCoordinate {
+            MapCoordinate::new(
+                self.robot.get_coordinate().get_col(),
+                self.robot.get_coordinate().get_row(),
+            )
+        }
+
+        fn energy_level(&self) -> usize {
+            self.robot.get_energy().get_energy_level()
+        }
+    }
+}
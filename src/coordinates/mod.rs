@@ -1,11 +1,28 @@
 pub mod map_coordinate {
-    use std::ops::{Add, Sub};
+    use crate::errors::tool_errors::ToolError;
+    use robotics_lib::interface::Direction;
+    use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
     /// The `MapCoordinate` struct represents coordinates within a two-dimensional map or grid.
     ///
     /// ## Fields
     ///
-    /// - `width`: An unsigned integer representing the number of columns in the coordinate system.
-    /// - `height`: An unsigned integer representing the number of rows in the coordinate system.
+    /// - `col`: An unsigned integer representing the column (x) of the coordinate.
+    /// - `row`: An unsigned integer representing the row (y) of the coordinate.
+    ///
+    /// The world map is indexed row first, so the tile at a coordinate is `map[row][col]`. The
+    /// default coordinate is the origin `(0, 0)`.
+    ///
+    /// ## Axes
+    ///
+    /// This is the one convention used by the whole crate, whatever the names in use:
+    ///
+    /// | accessor            | also known as | grows     | indexes           |
+    /// |---------------------|---------------|-----------|-------------------|
+    /// | `x()` / `col()`     | width         | rightward | `map[..][col]`    |
+    /// | `y()` / `row()`     | height        | downward  | `map[row]`        |
+    ///
+    /// So `x == col == width` and `y == row == height`. `MapCoordinate::new` and the `(usize,
+    /// usize)` conversions take the column first.
     ///
     /// ## Example
     ///
@@ -14,23 +31,23 @@ pub mod map_coordinate {
     /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
     /// let coordinate = MapCoordinate::new(8,3);
     ///
-    /// // Accessing width and height
-    /// println!("Width: {}", coordinate.get_width());
-    /// println!("Height: {}", coordinate.get_height());
+    /// // Accessing column and row
+    /// println!("Column: {}", coordinate.get_col());
+    /// println!("Row: {}", coordinate.get_row());
     /// ```
     ///
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
     pub struct MapCoordinate {
-        width: usize,
-        height: usize,
+        col: usize,
+        row: usize,
     }
     impl MapCoordinate {
-        /// Creates a new `MapCoordinate` instance with the given width and height.
+        /// Creates a new `MapCoordinate` instance with the given column and row.
         ///
         /// # Arguments
         ///
-        /// * `width` - The width of the map.
-        /// * `height` - The height of the map.
+        /// * `col` - The column (x) of the coordinate.
+        /// * `row` - The row (y) of the coordinate.
         ///
         /// # Example
         ///
@@ -38,78 +55,325 @@ pub mod map_coordinate {
         /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
         /// let coordinates = MapCoordinate::new(10, 20);
         /// ```
-        pub fn new(width: usize, height: usize) -> Self {
-            MapCoordinate { width, height }
+        pub fn new(col: usize, row: usize) -> Self {
+            MapCoordinate { col, row }
         }
 
-        /// Gets the width of the map coordinate.
+        /// Gets the column of the map coordinate.
         ///
         /// # Example
         ///
         /// ```
         /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
         /// let coordinates = MapCoordinate::new(10, 20);
-        /// let width = coordinates.get_width();
-        /// assert_eq!(width, 10);
+        /// let col = coordinates.get_col();
+        /// assert_eq!(col, 10);
         /// ```
-        pub fn get_width(&self) -> usize {
-            self.width
+        pub fn get_col(&self) -> usize {
+            self.col
         }
 
-        /// Sets the width of the map coordinate.
+        /// Sets the column of the map coordinate.
         ///
         /// # Arguments
         ///
-        /// * `width` - The new width value.
+        /// * `col` - The new column value.
         ///
         /// # Example
         ///
         /// ```
         /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
         /// let mut coordinates = MapCoordinate::new(10, 20);
-        /// coordinates.set_width(15);
-        /// assert_eq!(coordinates.get_width(), 15);
+        /// coordinates.set_col(15);
+        /// assert_eq!(coordinates.get_col(), 15);
         /// ```
-        pub fn set_width(&mut self, width: usize) {
-            self.width = width;
+        pub fn set_col(&mut self, col: usize) {
+            self.col = col;
         }
 
-        /// Gets the height of the map coordinate.
+        /// Gets the row of the map coordinate.
         ///
         /// # Example
         ///
         /// ```
         /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
         /// let coordinates = MapCoordinate::new(10, 20);
-        /// let height = coordinates.get_height();
-        /// assert_eq!(height, 20);
+        /// let row = coordinates.get_row();
+        /// assert_eq!(row, 20);
         /// ```
-        pub fn get_height(&self) -> usize {
-            self.height
+        pub fn get_row(&self) -> usize {
+            self.row
         }
 
-        /// Sets the height of the map coordinate.
+        /// Sets the row of the map coordinate.
         ///
         /// # Arguments
         ///
-        /// * `height` - The new height value.
+        /// * `row` - The new row value.
         ///
         /// # Example
         ///
         /// ```
         /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
         /// let mut coordinates = MapCoordinate::new(10, 20);
-        /// coordinates.set_height(25);
-        /// assert_eq!(coordinates.get_height(), 25);
+        /// coordinates.set_row(25);
+        /// assert_eq!(coordinates.get_row(), 25);
+        /// ```
+        pub fn set_row(&mut self, row: usize) {
+            self.row = row;
+        }
+
+        /// Returns the column of the coordinate, its `x`, see the [axes](MapCoordinate#axes).
+        pub fn col(&self) -> usize {
+            self.col
+        }
+
+        /// Returns the row of the coordinate, its `y`, see the [axes](MapCoordinate#axes).
+        pub fn row(&self) -> usize {
+            self.row
+        }
+
+        /// Returns the `x` of the coordinate, its column, see the [axes](MapCoordinate#axes).
+        pub fn x(&self) -> usize {
+            self.col
+        }
+
+        /// Returns the `y` of the coordinate, its row, see the [axes](MapCoordinate#axes).
+        pub fn y(&self) -> usize {
+            self.row
+        }
+
+        /// Returns the coordinate as a `(row, col)` pair, the order used to index the world map
+        /// as `map[row][col]`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let coordinates = MapCoordinate::new(10, 20);
+        /// assert_eq!(coordinates.as_row_col(), (20, 10));
+        /// ```
+        pub fn as_row_col(&self) -> (usize, usize) {
+            (self.row, self.col)
+        }
+
+        /// Returns the coordinate as a `(col, row)` pair, the order of the keys of the map
+        /// returned by the scanner discovery and of `Into<(usize, usize)>`.
+        ///
+        /// # Example
+        ///
         /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let coordinates = MapCoordinate::new(10, 20);
+        /// assert_eq!(coordinates.as_col_row(), (10, 20));
+        /// ```
+        pub fn as_col_row(&self) -> (usize, usize) {
+            (self.col, self.row)
+        }
+
+        /// Gets the column of the map coordinate.
+        #[deprecated(note = "the width is the column, use `get_col` instead")]
+        pub fn get_width(&self) -> usize {
+            self.col
+        }
+
+        /// Sets the column of the map coordinate.
+        #[deprecated(note = "the width is the column, use `set_col` instead")]
+        pub fn set_width(&mut self, width: usize) {
+            self.col = width;
+        }
+
+        /// Gets the row of the map coordinate.
+        #[deprecated(note = "the height is the row, use `get_row` instead")]
+        pub fn get_height(&self) -> usize {
+            self.row
+        }
+
+        /// Sets the row of the map coordinate.
+        #[deprecated(note = "the height is the row, use `set_row` instead")]
         pub fn set_height(&mut self, height: usize) {
-            self.height = height;
+            self.row = height;
         }
-    }
 
-    impl PartialEq for MapCoordinate {
-        fn eq(&self, other: &Self) -> bool {
-            self.height == other.height && self.width == other.width
+        /// Returns the coordinate one tile away in the given direction.
+        ///
+        /// `Up` and `Down` change the row, `Left` and `Right` change the column.
+        ///
+        /// # Arguments
+        ///
+        /// * `direction` - The direction of the step.
+        ///
+        /// # Returns
+        ///
+        /// Returns `None` if the step would make the column or the row negative.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// use robotics_lib::interface::Direction;
+        /// let coordinates = MapCoordinate::new(10, 0);
+        /// assert_eq!(coordinates.step(Direction::Right), Some(MapCoordinate::new(11, 0)));
+        /// assert_eq!(coordinates.step(Direction::Up), None);
+        /// ```
+        pub fn step(&self, direction: Direction) -> Option<MapCoordinate> {
+            match direction {
+                Direction::Up => Some(MapCoordinate::new(self.col, self.row.checked_sub(1)?)),
+                Direction::Down => Some(MapCoordinate::new(self.col, self.row + 1)),
+                Direction::Left => Some(MapCoordinate::new(self.col.checked_sub(1)?, self.row)),
+                Direction::Right => Some(MapCoordinate::new(self.col + 1, self.row)),
+            }
+        }
+
+        /// Returns the direction of the step that reduces the distance to `other` the most.
+        ///
+        /// When `other` is diagonal to `self`, the axis with the larger delta is chosen. If both
+        /// deltas are equal, the horizontal direction (`Left` or `Right`) is preferred.
+        ///
+        /// # Arguments
+        ///
+        /// * `other` - The target coordinate.
+        ///
+        /// # Returns
+        ///
+        /// Returns `None` if `other` is equal to `self`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// use robotics_lib::interface::Direction;
+        /// let robot = MapCoordinate::new(5, 5);
+        /// assert_eq!(robot.direction_to(&MapCoordinate::new(6, 9)), Some(Direction::Down));
+        /// assert_eq!(robot.direction_to(&robot), None);
+        /// ```
+        pub fn direction_to(&self, other: &MapCoordinate) -> Option<Direction> {
+            let col_delta = self.col.abs_diff(other.col);
+            let row_delta = self.row.abs_diff(other.row);
+            if col_delta == 0 && row_delta == 0 {
+                return None;
+            }
+            if col_delta >= row_delta {
+                if other.col > self.col {
+                    Some(Direction::Right)
+                } else {
+                    Some(Direction::Left)
+                }
+            } else if other.row > self.row {
+                Some(Direction::Down)
+            } else {
+                Some(Direction::Up)
+            }
+        }
+
+        /// Returns the Manhattan distance to `other`, i.e. the number of steps needed to reach it
+        /// moving up, down, left and right only.
+        ///
+        /// # Arguments
+        ///
+        /// * `other` - The other coordinate.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let robot = MapCoordinate::new(5, 5);
+        /// assert_eq!(robot.manhattan_distance(&MapCoordinate::new(2, 9)), 7);
+        /// ```
+        pub fn manhattan_distance(&self, other: &MapCoordinate) -> usize {
+            self.col.abs_diff(other.col) + self.row.abs_diff(other.row)
+        }
+
+        /// Returns the Chebyshev distance to `other`, i.e. the larger of the column and row
+        /// deltas. A tile at distance `d` lies on the border of the `Area(2 * d + 1)` centered on
+        /// `self`.
+        ///
+        /// # Arguments
+        ///
+        /// * `other` - The other coordinate.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let robot = MapCoordinate::new(5, 5);
+        /// assert_eq!(robot.chebyshev_distance(&MapCoordinate::new(2, 9)), 4);
+        /// ```
+        pub fn chebyshev_distance(&self, other: &MapCoordinate) -> usize {
+            self.col.abs_diff(other.col).max(self.row.abs_diff(other.row))
+        }
+
+        /// Returns the coordinate at the signed offset `(dx, dy)` from this one, or `None` if it
+        /// would be negative or not below `bounds`, given as `(columns, rows)`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let coordinate = MapCoordinate::new(2, 2);
+        /// assert_eq!(coordinate.try_offset(-2, 1, (5, 5)), Some(MapCoordinate::new(0, 3)));
+        /// assert_eq!(coordinate.try_offset(-3, 0, (5, 5)), None);
+        /// assert_eq!(coordinate.try_offset(0, 3, (5, 5)), None);
+        /// ```
+        pub fn try_offset(&self, dx: i64, dy: i64, bounds: (usize, usize)) -> Option<MapCoordinate> {
+            let col = self.col.checked_add_signed(isize::try_from(dx).ok()?)?;
+            let row = self.row.checked_add_signed(isize::try_from(dy).ok()?)?;
+            (col < bounds.0 && row < bounds.1).then(|| MapCoordinate::new(col, row))
+        }
+
+        /// Returns the index of the coordinate in a flat, row-major storage of a world
+        /// `world_width` columns wide, i.e. `row * world_width + col`.
+        ///
+        /// # Arguments
+        ///
+        /// * `world_width` - The number of columns of the world.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let coordinate = MapCoordinate::new(3, 2);
+        /// assert_eq!(coordinate.to_index(10), 23);
+        /// assert_eq!(MapCoordinate::from_index(23, 10), coordinate);
+        /// ```
+        pub fn to_index(&self, world_width: usize) -> usize {
+            self.row * world_width + self.col
+        }
+
+        /// Returns the coordinate at the given index of a flat, row-major storage of a world
+        /// `world_width` columns wide, the inverse of [`MapCoordinate::to_index`].
+        ///
+        /// # Arguments
+        ///
+        /// * `index` - The index in the flat storage.
+        /// * `world_width` - The number of columns of the world, not 0.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// assert_eq!(MapCoordinate::from_index(7, 5), MapCoordinate::new(2, 1));
+        /// ```
+        pub fn from_index(index: usize, world_width: usize) -> MapCoordinate {
+            MapCoordinate::new(index % world_width, index / world_width)
+        }
+
+        /// Returns an iterator over the coordinates of the rectangle between `from` and `to`,
+        /// both included, by row, then by column.
+        ///
+        /// The iterator is empty if `to` lies left of or above `from`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let rect: Vec<(usize, usize)> = MapCoordinate::iter_rect(MapCoordinate::new(1, 4), MapCoordinate::new(2, 5))
+        ///     .map(|coordinate| coordinate.as_col_row())
+        ///     .collect();
+        /// assert_eq!(rect, vec![(1, 4), (2, 4), (1, 5), (2, 5)]);
+        /// ```
+        pub fn iter_rect(from: MapCoordinate, to: MapCoordinate) -> impl Iterator<Item = MapCoordinate> {
+            (from.row..=to.row).flat_map(move |row| (from.col..=to.col).map(move |col| MapCoordinate::new(col, row)))
         }
     }
 
@@ -117,8 +381,8 @@ pub mod map_coordinate {
         type Output = Self;
         fn add(self, rhs: Self) -> Self::Output {
             Self {
-                width: self.width + rhs.width,
-                height: self.height + rhs.height,
+                col: self.col + rhs.col,
+                row: self.row + rhs.row,
             }
         }
     }
@@ -127,24 +391,364 @@ pub mod map_coordinate {
         type Output = Self;
         fn sub(self, rhs: Self) -> Self::Output {
             Self {
-                width: self.width - rhs.width,
-                height: self.height - rhs.height,
+                col: self.col - rhs.col,
+                row: self.row - rhs.row,
+            }
+        }
+    }
+
+    impl AddAssign for MapCoordinate {
+        fn add_assign(&mut self, rhs: Self) {
+            *self = *self + rhs;
+        }
+    }
+
+    /// Subtracts in place like `Sub`, so it overflows the same way when `rhs` lies right of or
+    /// below the coordinate; use [`MapCoordinate::step`] to move without leaving the map.
+    impl SubAssign for MapCoordinate {
+        fn sub_assign(&mut self, rhs: Self) {
+            *self = *self - rhs;
+        }
+    }
+
+    /// Scales both the column and the row, e.g. to repeat an offset a number of steps.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+    /// assert_eq!(MapCoordinate::new(1, 2) * 3, MapCoordinate::new(3, 6));
+    /// ```
+    impl Mul<usize> for MapCoordinate {
+        type Output = Self;
+        fn mul(self, rhs: usize) -> Self::Output {
+            Self {
+                col: self.col * rhs,
+                row: self.row * rhs,
             }
         }
     }
 
+    /// Builds a coordinate from a `(col, row)` pair.
     impl From<(usize, usize)> for MapCoordinate {
         fn from(value: (usize, usize)) -> Self {
             Self {
-                width: value.0,
-                height: value.1,
+                col: value.0,
+                row: value.1,
+            }
+        }
+    }
+
+    /// Builds a coordinate from a signed `(col, row)` pair, e.g. the result of offset math.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ToolError::NegativeCoordinate` if either component is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+    /// assert_eq!(MapCoordinate::try_from((3, 4)).unwrap(), MapCoordinate::new(3, 4));
+    /// assert!(MapCoordinate::try_from((-1, 4)).is_err());
+    /// ```
+    impl TryFrom<(i32, i32)> for MapCoordinate {
+        type Error = ToolError;
+        fn try_from(value: (i32, i32)) -> Result<Self, Self::Error> {
+            match (usize::try_from(value.0), usize::try_from(value.1)) {
+                (Ok(col), Ok(row)) => Ok(Self { col, row }),
+                _ => Err(ToolError::NegativeCoordinate(value.0, value.1)),
             }
         }
     }
 
+    /// Converts the coordinate into a `(col, row)` pair, see [`MapCoordinate::as_col_row`] and
+    /// [`MapCoordinate::as_row_col`] to make the order explicit.
     impl Into<(usize, usize)> for MapCoordinate {
         fn into(self) -> (usize, usize) {
-            (self.width, self.height)
+            self.as_col_row()
+        }
+    }
+}
+
+pub mod coordinatelike {
+    use crate::coordinates::map_coordinate::MapCoordinate;
+
+    /// The `Coordinatelike` trait abstracts over the coordinate types the pattern math works
+    /// with, so that engines with their own coordinate type can use the scanner without
+    /// converting to and from [`MapCoordinate`] at every call.
+    ///
+    /// Like `MapCoordinate`, the column is the x and the row is the y of the tile, so the tile at
+    /// a coordinate is `map[row][col]`. Coordinates must be `Send` to be computed in parallel
+    /// with the `rayon` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::coordinates::coordinatelike::Coordinatelike;
+    /// #[derive(Debug, Clone, Copy, PartialEq)]
+    /// struct Cell { x: usize, y: usize }
+    /// impl Coordinatelike for Cell {
+    ///     fn from_col_row(col: usize, row: usize) -> Self { Cell { x: col, y: row } }
+    ///     fn col(&self) -> usize { self.x }
+    ///     fn row(&self) -> usize { self.y }
+    /// }
+    /// assert_eq!(Cell::from_col_row(2, 7), Cell { x: 2, y: 7 });
+    /// ```
+    pub trait Coordinatelike: Copy + Send {
+        /// Creates the coordinate of the tile at the given column and row.
+        fn from_col_row(col: usize, row: usize) -> Self;
+
+        /// Returns the column (x) of the coordinate.
+        fn col(&self) -> usize;
+
+        /// Returns the row (y) of the coordinate.
+        fn row(&self) -> usize;
+    }
+
+    impl Coordinatelike for MapCoordinate {
+        fn from_col_row(col: usize, row: usize) -> Self {
+            MapCoordinate::new(col, row)
+        }
+
+        fn col(&self) -> usize {
+            MapCoordinate::col(self)
+        }
+
+        fn row(&self) -> usize {
+            MapCoordinate::row(self)
+        }
+    }
+}
+
+pub mod relative_coordinate {
+    use crate::coordinates::map_coordinate::MapCoordinate;
+
+    /// The `RelativeCoordinate` struct represents the offset of a tile from a reference position,
+    /// usually the position of the robot.
+    ///
+    /// ## Fields
+    ///
+    /// - `col`: The column offset, positive to the right and negative to the left.
+    /// - `row`: The row offset, positive downwards and negative upwards, since rows grow towards
+    ///   the bottom of the world map.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+    /// use resource_scanner_tool::coordinates::relative_coordinate::RelativeCoordinate;
+    /// let robot = MapCoordinate::new(5, 5);
+    /// let offset = RelativeCoordinate::between(&robot, &MapCoordinate::new(3, 6));
+    /// assert_eq!(offset, RelativeCoordinate::new(-2, 1));
+    /// assert_eq!(offset.resolve(&robot), Some(MapCoordinate::new(3, 6)));
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RelativeCoordinate {
+        col: isize,
+        row: isize,
+    }
+
+    impl RelativeCoordinate {
+        /// Creates a new `RelativeCoordinate` with the given column and row offsets.
+        pub fn new(col: isize, row: isize) -> Self {
+            RelativeCoordinate { col, row }
+        }
+
+        /// Returns the offset of `target` from `origin`.
+        pub fn between(origin: &MapCoordinate, target: &MapCoordinate) -> Self {
+            RelativeCoordinate {
+                col: target.get_col() as isize - origin.get_col() as isize,
+                row: target.get_row() as isize - origin.get_row() as isize,
+            }
+        }
+
+        /// Gets the column offset.
+        pub fn get_col(&self) -> isize {
+            self.col
+        }
+
+        /// Gets the row offset.
+        pub fn get_row(&self) -> isize {
+            self.row
+        }
+
+        /// Returns the absolute coordinate at this offset from `origin`, or `None` if the column
+        /// or the row would be negative.
+        pub fn resolve(&self, origin: &MapCoordinate) -> Option<MapCoordinate> {
+            Some(MapCoordinate::new(
+                origin.get_col().checked_add_signed(self.col)?,
+                origin.get_row().checked_add_signed(self.row)?,
+            ))
+        }
+    }
+}
+
+pub mod coordinate_set {
+    use crate::coordinates::map_coordinate::MapCoordinate;
+
+    /// The `CoordinateSet` struct is a set of coordinates backed by a bit vector, using one bit per
+    /// tile of the world instead of the tens of bytes per entry of a `HashSet<MapCoordinate>`.
+    ///
+    /// The set is sized to the dimensions given to [`CoordinateSet::new`], and grows when a
+    /// coordinate outside them is inserted. Iteration visits the coordinates by row, then by
+    /// column. Two sets are equal when they hold the same coordinates, whatever their
+    /// dimensions.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::coordinates::coordinate_set::CoordinateSet;
+    /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+    /// let mut set = CoordinateSet::new(1000, 1000);
+    /// assert!(set.insert(MapCoordinate::new(4, 2)));
+    /// assert!(!set.insert(MapCoordinate::new(4, 2)));
+    /// assert!(set.contains(&MapCoordinate::new(4, 2)));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    #[derive(Debug, Clone, Default)]
+    pub struct CoordinateSet {
+        width: usize,
+        height: usize,
+        bits: Vec<u64>,
+        len: usize,
+    }
+
+    impl CoordinateSet {
+        /// Creates an empty `CoordinateSet` for a world of `width` columns and `height` rows.
+        pub fn new(width: usize, height: usize) -> Self {
+            Self {
+                width,
+                height,
+                bits: vec![0; (width * height).div_ceil(64)],
+                len: 0,
+            }
+        }
+
+        /// Adds a coordinate to the set, growing it if the coordinate lies outside its
+        /// dimensions.
+        ///
+        /// # Returns
+        ///
+        /// Returns `true` if the coordinate was not in the set yet.
+        pub fn insert(&mut self, coordinate: MapCoordinate) -> bool {
+            if coordinate.get_col() >= self.width || coordinate.get_row() >= self.height {
+                self.grow(coordinate);
+            }
+            let (word, mask) = self.position(&coordinate);
+            if self.bits[word] & mask != 0 {
+                return false;
+            }
+            self.bits[word] |= mask;
+            self.len += 1;
+            true
+        }
+
+        /// Removes a coordinate from the set.
+        ///
+        /// # Returns
+        ///
+        /// Returns `true` if the coordinate was in the set.
+        pub fn remove(&mut self, coordinate: &MapCoordinate) -> bool {
+            if !self.contains(coordinate) {
+                return false;
+            }
+            let (word, mask) = self.position(coordinate);
+            self.bits[word] &= !mask;
+            self.len -= 1;
+            true
+        }
+
+        /// Checks whether the set holds the coordinate.
+        pub fn contains(&self, coordinate: &MapCoordinate) -> bool {
+            if coordinate.get_col() >= self.width || coordinate.get_row() >= self.height {
+                return false;
+            }
+            let (word, mask) = self.position(coordinate);
+            self.bits[word] & mask != 0
+        }
+
+        /// Returns the number of coordinates in the set.
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Checks whether the set is empty.
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        /// Removes every coordinate, keeping the dimensions of the set.
+        pub fn clear(&mut self) {
+            self.bits.iter_mut().for_each(|word| *word = 0);
+            self.len = 0;
+        }
+
+        /// Returns an iterator over the coordinates of the set, by row, then by column.
+        ///
+        /// The empty words are skipped, so that a sparse set over a large world is walked quickly.
+        pub fn iter(&self) -> impl Iterator<Item = MapCoordinate> + '_ {
+            self.bits.iter().enumerate().flat_map(move |(word, &bits)| {
+                let mut remaining = bits;
+                std::iter::from_fn(move || {
+                    if remaining == 0 {
+                        return None;
+                    }
+                    let bit = remaining.trailing_zeros() as usize;
+                    remaining &= remaining - 1;
+                    Some(MapCoordinate::from_index(word * 64 + bit, self.width))
+                })
+            })
+        }
+
+        /// Returns the word and the mask of the bit of an in-bounds coordinate.
+        fn position(&self, coordinate: &MapCoordinate) -> (usize, u64) {
+            let index = coordinate.to_index(self.width);
+            (index / 64, 1 << (index % 64))
+        }
+
+        /// Enlarges the set to hold the coordinate, at least doubling the exceeded dimensions so
+        /// that a growing sequence of insertions does not move the bits every time.
+        fn grow(&mut self, coordinate: MapCoordinate) {
+            let width = if coordinate.get_col() < self.width {
+                self.width
+            } else {
+                (coordinate.get_col() + 1).max(self.width * 2)
+            };
+            let height = if coordinate.get_row() < self.height {
+                self.height
+            } else {
+                (coordinate.get_row() + 1).max(self.height * 2)
+            };
+            let mut grown = CoordinateSet::new(width, height);
+            for existing in self.iter() {
+                grown.insert(existing);
+            }
+            *self = grown;
+        }
+    }
+
+    /// Compares the members of the sets, so that a set grown by an insertion equals a set
+    /// created with the larger dimensions.
+    impl PartialEq for CoordinateSet {
+        fn eq(&self, other: &Self) -> bool {
+            self.len == other.len && self.iter().all(|coordinate| other.contains(&coordinate))
+        }
+    }
+
+    impl Eq for CoordinateSet {}
+
+    /// Builds a set sized to the largest column and row of the coordinates.
+    impl FromIterator<MapCoordinate> for CoordinateSet {
+        fn from_iter<I: IntoIterator<Item = MapCoordinate>>(iter: I) -> Self {
+            let coordinates: Vec<MapCoordinate> = iter.into_iter().collect();
+            let width = coordinates.iter().map(|c| c.get_col() + 1).max().unwrap_or(0);
+            let height = coordinates.iter().map(|c| c.get_row() + 1).max().unwrap_or(0);
+            let mut set = CoordinateSet::new(width, height);
+            for coordinate in coordinates {
+                set.insert(coordinate);
+            }
+            set
         }
     }
 }
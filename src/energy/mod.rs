@@ -0,0 +1,75 @@
+pub mod energy_budget {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// The `EnergyBudget` struct holds the energy a robot is willing to spend on its tools, so
+    /// that several tools used in the same tick draw from a shared amount instead of competing
+    /// for the energy blindly.
+    ///
+    /// Cloning a budget returns a handle to the same remaining units: reserving through one clone
+    /// reduces the units seen by all the others. The handles are shared instead of borrowed
+    /// mutably, so that a [`ScanRequest`](crate::tool::resource_scanner::ScanRequest) can hold
+    /// one, and the units are atomic, so that the handles, and the requests holding them, are
+    /// `Send` and `Sync`.
+    ///
+    /// This sharing is by design: [`ScanRequest::budget`](crate::tool::resource_scanner::ScanRequest::budget)
+    /// keeps a handle rather than a copy, so the reservation of a scan and the refund of the part
+    /// it did not spend are both seen through the handle kept by the caller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::energy::energy_budget::EnergyBudget;
+    /// let budget = EnergyBudget::new(100);
+    /// let shared = budget.clone();
+    /// assert!(shared.try_reserve(80));
+    /// assert!(!budget.try_reserve(30));
+    /// budget.refund(10);
+    /// assert_eq!(budget.remaining(), 30);
+    /// ```
+    #[derive(Debug, Clone, Default)]
+    pub struct EnergyBudget {
+        remaining: Arc<AtomicUsize>,
+    }
+
+    /// Two budgets are equal if they are handles to the same units, such as a budget and its
+    /// clones, whatever the units left: separate budgets holding the same amount differ.
+    impl PartialEq for EnergyBudget {
+        fn eq(&self, other: &Self) -> bool {
+            Arc::ptr_eq(&self.remaining, &other.remaining)
+        }
+    }
+
+    impl Eq for EnergyBudget {}
+
+    impl EnergyBudget {
+        /// Creates a new `EnergyBudget` with the given units of energy.
+        pub fn new(units: usize) -> Self {
+            Self {
+                remaining: Arc::new(AtomicUsize::new(units)),
+            }
+        }
+
+        /// Returns the units of energy still available.
+        pub fn remaining(&self) -> usize {
+            self.remaining.load(Ordering::Acquire)
+        }
+
+        /// Reserves `units` of energy, if available.
+        ///
+        /// # Returns
+        ///
+        /// Returns `true` if the units were reserved, `false` if fewer units are available, in
+        /// which case the budget is left unchanged.
+        pub fn try_reserve(&self, units: usize) -> bool {
+            self.remaining
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |remaining| remaining.checked_sub(units))
+                .is_ok()
+        }
+
+        /// Gives back `units` of energy, e.g. the part of a reservation that was not spent.
+        pub fn refund(&self, units: usize) {
+            self.remaining.fetch_add(units, Ordering::AcqRel);
+        }
+    }
+}
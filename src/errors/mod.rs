@@ -1,13 +1,47 @@
 pub mod tool_errors {
+    use crate::coordinates::map_coordinate::MapCoordinate;
+    use robotics_lib::utils::LibError;
     use std::error::Error;
     use std::fmt::{Debug, Display, Formatter};
 
+    /// The result of the fallible operations of the scanner.
+    pub type ScanOutcome<T> = Result<T, ToolError>;
+
+    /// The errors of the scanner.
+    ///
+    /// The enum is `#[non_exhaustive]`: new variants may be added in minor releases, so matches
+    /// on it need a wildcard arm. Errors compare field-wise, so tests can `assert_eq!` them.
+    ///
+    /// # Variants
+    ///
+    /// - `InvalidSizeError { size, world_size }`: The size of the pattern is not valid in a world
+    ///   of `world_size`.
+    /// - `NotEnoughEnergy { required, available }`: Discovering the tiles needs `required`
+    ///   energy, but the robot has only `available`.
+    /// - `NoMoreDiscovery { requested }`: The robot cannot discover the `requested` tiles, having
+    ///   discovered as many tiles as the world allows.
+    /// - `MapUnavailable`: The map of the tiles known to the robot cannot be read.
+    /// - `Cancelled`: The scan was stopped by the caller before it completed, see
+    ///   `ResourceScanner::scan_async_cancellable`.
+    /// - `ContentNotSupported`: Deprecated, every content can be scanned and no scan returns it.
+    /// - `Interface(LibError)`: Another error of the `robotics_lib` interfaces, kept as is so
+    ///   that callers can still match on it.
+    #[derive(Clone, PartialEq)]
+    #[non_exhaustive]
     pub enum ToolError {
-        InvalidSizeError,
+        InvalidSizeError { size: usize, world_size: usize },
         EmptyCoordinates,
-        NotEnoughEnergy,
-        NoMoreDiscovery,
+        NotEnoughEnergy { required: usize, available: usize },
+        NoMoreDiscovery { requested: usize },
+        #[deprecated(since = "1.1.0", note = "every content can be scanned, no scan returns this error")]
         ContentNotSupported,
+        OutOfBounds(Vec<MapCoordinate>),
+        NegativeCoordinate(i32, i32),
+        EnergyCapExceeded { estimated: usize, cap: usize },
+        BudgetExhausted { estimated: usize, remaining: usize },
+        MapUnavailable,
+        Cancelled,
+        Interface(LibError),
         Other(String),
     }
 
@@ -21,14 +55,100 @@ pub mod tool_errors {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
             return match self {
                 ToolError::EmptyCoordinates => write!(f,"{}","Empty Coordinates".to_string()),
-                ToolError::NotEnoughEnergy => write!(f,"{}","Not Enough Energy".to_string()),
+                ToolError::NotEnoughEnergy { required, available } => {
+                    write!(f, "Not Enough Energy: {} required, {} available", required, available)
+                }
                 ToolError::Other(message) => write!(f, "{}", message),
-                ToolError::InvalidSizeError => write!(f,"Invalid Size"),
-                ToolError::NoMoreDiscovery => write!(f,"{}","No More Discovery".to_string()),
-                ToolError::ContentNotSupported => write!(f,"{}","The used content is not supported".to_string())
+                ToolError::InvalidSizeError { size, world_size } => {
+                    write!(f, "Invalid Size: {} in a world of size {}", size, world_size)
+                }
+                ToolError::NoMoreDiscovery { requested } => write!(f, "No More Discovery: {} tiles requested", requested),
+                #[allow(deprecated)]
+                ToolError::ContentNotSupported => write!(f,"{}","The used content is not supported".to_string()),
+                ToolError::OutOfBounds(coordinates) => {
+                    let list: Vec<String> = coordinates
+                        .iter()
+                        .map(|c| format!("({}, {})", c.get_col(), c.get_row()))
+                        .collect();
+                    if list.is_empty() {
+                        return write!(f, "Out Of Bounds");
+                    }
+                    write!(f, "Out Of Bounds: {}", list.join(", "))
+                }
+                ToolError::NegativeCoordinate(col, row) => write!(f, "Negative Coordinate: ({}, {})", col, row),
+                ToolError::MapUnavailable => write!(f, "Map Unavailable"),
+                ToolError::Cancelled => write!(f, "Cancelled"),
+                ToolError::Interface(error) => write!(f, "Interface Error: {:?}", error),
+                ToolError::EnergyCapExceeded { estimated, cap } => {
+                    write!(f, "Energy Cap Exceeded: {} > {}", estimated, cap)
+                }
+                ToolError::BudgetExhausted { estimated, remaining } => {
+                    write!(f, "Budget Exhausted: {} > {}", estimated, remaining)
+                }
             };
         }
     }
 
     impl Error for ToolError {}
+
+    /// Converts an error of the `robotics_lib` interfaces into the matching `ToolError`.
+    ///
+    /// `NotEnoughEnergy`, `NoMoreDiscovery` and `OutOfBounds` become the variants of the same
+    /// name. The conversion knows nothing about the failed operation, so their amounts are 0 and
+    /// their coordinates empty; the scans fill them in with the context of the discovery. Every
+    /// other error is kept as is in `ToolError::Interface`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::errors::tool_errors::ToolError;
+    /// use robotics_lib::utils::LibError;
+    /// let error = ToolError::from(LibError::OutOfBounds);
+    /// assert_eq!(error, ToolError::OutOfBounds(vec![]));
+    /// let error = ToolError::from(LibError::CannotWalk);
+    /// assert!(matches!(error, ToolError::Interface(LibError::CannotWalk)));
+    /// ```
+    impl From<LibError> for ToolError {
+        fn from(error: LibError) -> Self {
+            match error {
+                LibError::NotEnoughEnergy => ToolError::NotEnoughEnergy {
+                    required: 0,
+                    available: 0,
+                },
+                LibError::NoMoreDiscovery => ToolError::NoMoreDiscovery { requested: 0 },
+                LibError::OutOfBounds => ToolError::OutOfBounds(Vec::new()),
+                other => ToolError::Interface(other),
+            }
+        }
+    }
+
+    /// The `PatternParseError` enum describes why a string could not be parsed as a
+    /// [`Pattern`](crate::tool::resource_scanner::Pattern).
+    ///
+    /// # Variants
+    ///
+    /// - `Malformed(String)`: The input, lacking the `:` between the name and the size.
+    /// - `UnknownName(String)`: The name, which is not one of the pattern names.
+    /// - `InvalidSize(String)`: The size, which is not a number (or a pair of offsets for
+    ///   `single`, a list of offsets for `custom`).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum PatternParseError {
+        Malformed(String),
+        UnknownName(String),
+        InvalidSize(String),
+    }
+
+    impl Display for PatternParseError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                PatternParseError::Malformed(input) => {
+                    write!(f, "Malformed Pattern: \"{}\", expected name:size", input)
+                }
+                PatternParseError::UnknownName(name) => write!(f, "Unknown Pattern: \"{}\"", name),
+                PatternParseError::InvalidSize(size) => write!(f, "Invalid Pattern Size: \"{}\"", size),
+            }
+        }
+    }
+
+    impl Error for PatternParseError {}
 }
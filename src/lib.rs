@@ -1,6 +1,11 @@
+pub mod backend;
 pub mod coordinates;
+pub mod energy;
 pub mod errors;
+pub mod prelude;
+#[cfg(any(test, feature = "test_utils"))]
+pub mod test_utils;
 #[cfg(test)]
 mod tests;
 pub mod tool;
-mod utils;
+pub mod utils;
@@ -0,0 +1,14 @@
+//! The types needed by most users of the tool, to be imported at once.
+//!
+//! ```
+//! use resource_scanner_tool::prelude::*;
+//! let scanner = ResourceScanner::new();
+//! let pattern = Pattern::Area(5);
+//! let coordinate = MapCoordinate::new(8, 3);
+//! let error = ToolError::InvalidSizeError { size: 4, world_size: 10 };
+//! assert_eq!(error.to_string(), "Invalid Size: 4 in a world of size 10");
+//! ```
+pub use crate::coordinates::map_coordinate::MapCoordinate;
+pub use crate::energy::energy_budget::EnergyBudget;
+pub use crate::errors::tool_errors::{ScanOutcome, ToolError};
+pub use crate::tool::resource_scanner::{Pattern, ResourceScanner};
@@ -0,0 +1,268 @@
+pub mod test_world {
+    use crate::backend::discovery_backend::DiscoveryBackend;
+    use crate::coordinates::map_coordinate::MapCoordinate;
+    use robotics_lib::energy::Energy;
+    use robotics_lib::event::events::Event;
+    use robotics_lib::runner::backpack::BackPack;
+    use robotics_lib::runner::{Robot, Runnable, Runner};
+    use robotics_lib::utils::LibError;
+    use robotics_lib::world::coordinates::Coordinate;
+    use robotics_lib::world::environmental_conditions::EnvironmentalConditions;
+    use robotics_lib::world::environmental_conditions::WeatherType::Sunny;
+    use robotics_lib::world::tile::{Content, Tile, TileType};
+    use robotics_lib::world::world_generator::Generator;
+    use robotics_lib::world::world_generator::World as WorldType;
+    use robotics_lib::world::World;
+    use std::collections::HashMap;
+
+    /// The `FlatWorldGenerator` struct generates a deterministic square world where every tile
+    /// has the same type, no content and elevation 0, except for the tiles set with its builder
+    /// methods.
+    ///
+    /// It is available with the `test_utils` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::prelude::*;
+    /// use resource_scanner_tool::test_utils::test_world::{FlatWorldGenerator, ScannerTestRobot};
+    /// use robotics_lib::world::tile::{Content, TileType};
+    ///
+    /// let mut generator = FlatWorldGenerator::new(10, MapCoordinate::new(1, 1), TileType::Grass)
+    ///     .with_content(2, 2, Content::Coin(1))
+    ///     .with_content(0, 1, Content::Coin(3));
+    /// let robot = ScannerTestRobot::new(|robot, world| {
+    ///     let result = ResourceScanner::new().scan(world, robot, &Pattern::Area(3), &Content::Coin(0));
+    ///     assert_eq!(Some((MapCoordinate::new(0, 1), 3)), result.unwrap());
+    /// });
+    /// let _ = robot.into_runner(&mut generator).unwrap().game_tick();
+    /// ```
+    pub struct FlatWorldGenerator {
+        size: usize,
+        spawn: MapCoordinate,
+        tile_type: TileType,
+        tiles: Vec<(MapCoordinate, Tile)>,
+    }
+
+    impl FlatWorldGenerator {
+        /// Creates a new `FlatWorldGenerator`.
+        ///
+        /// # Arguments
+        ///
+        /// * `size` - The side of the square world.
+        /// * `spawn` - The coordinate where the robot is spawned.
+        /// * `tile_type` - The type of every tile not set otherwise.
+        pub fn new(size: usize, spawn: MapCoordinate, tile_type: TileType) -> Self {
+            Self {
+                size,
+                spawn,
+                tile_type,
+                tiles: Vec::new(),
+            }
+        }
+
+        /// Places the given content on the tile at column `x` and row `y`, keeping the tile type
+        /// of the world and elevation 0.
+        pub fn with_content(self, x: usize, y: usize, content: Content) -> Self {
+            let tile = Tile {
+                tile_type: self.tile_type,
+                content,
+                elevation: 0,
+            };
+            self.with_tile(x, y, tile)
+        }
+
+        /// Replaces the tile at column `x` and row `y` with the given one, e.g. to change its type
+        /// or elevation.
+        pub fn with_tile(mut self, x: usize, y: usize, tile: Tile) -> Self {
+            self.tiles.push((MapCoordinate::new(x, y), tile));
+            self
+        }
+    }
+
+    impl Generator for FlatWorldGenerator {
+        fn gen(&mut self) -> WorldType {
+            let default_tile = Tile {
+                tile_type: self.tile_type,
+                content: Content::None,
+                elevation: 0,
+            };
+            let mut map = vec![vec![default_tile; self.size]; self.size];
+            // the map is indexed row first, later tiles override earlier ones
+            for (coordinate, tile) in self.tiles.iter() {
+                map[coordinate.row()][coordinate.col()] = tile.clone();
+            }
+            let environmental_conditions =
+                EnvironmentalConditions::new(&vec![Sunny], 15, 12).unwrap();
+            (
+                map,
+                self.spawn.as_row_col(),
+                environmental_conditions,
+                10.0,
+                None,
+            )
+        }
+    }
+
+    /// The closure run by a [`ScannerTestRobot`] at every tick.
+    type OnTick = Box<dyn FnMut(&mut ScannerTestRobot, &mut World)>;
+
+    /// The `ScannerTestRobot` struct is a robot running a closure at every tick, which receives
+    /// the robot itself and the world, e.g. to perform a scan and check its result.
+    ///
+    /// It is available with the `test_utils` feature. See [`FlatWorldGenerator`] for an example.
+    pub struct ScannerTestRobot {
+        robot: Robot,
+        on_tick: Option<OnTick>,
+    }
+
+    impl ScannerTestRobot {
+        /// Creates a new `ScannerTestRobot` running `on_tick` at every tick.
+        pub fn new(on_tick: impl FnMut(&mut ScannerTestRobot, &mut World) + 'static) -> Self {
+            Self {
+                robot: Robot::new(),
+                on_tick: Some(Box::new(on_tick)),
+            }
+        }
+
+        /// Creates a `Runner` for the robot in the world built by `generator`.
+        pub fn into_runner(self, generator: &mut impl Generator) -> Result<Runner, LibError> {
+            Runner::new(Box::new(self), generator)
+        }
+    }
+
+    impl Runnable for ScannerTestRobot {
+        fn process_tick(&mut self, world: &mut World) {
+            // take the closure out for the duration of the call, as it borrows the robot
+            if let Some(mut on_tick) = self.on_tick.take() {
+                on_tick(self, world);
+                self.on_tick = Some(on_tick);
+            }
+        }
+        fn handle_event(&mut self, _event: Event) {}
+        fn get_energy(&self) -> &Energy {
+            &self.robot.energy
+        }
+        fn get_energy_mut(&mut self) -> &mut Energy {
+            &mut self.robot.energy
+        }
+        fn get_coordinate(&self) -> &Coordinate {
+            &self.robot.coordinate
+        }
+        fn get_coordinate_mut(&mut self) -> &mut Coordinate {
+            &mut self.robot.coordinate
+        }
+        fn get_backpack(&self) -> &BackPack {
+            &self.robot.backpack
+        }
+        fn get_backpack_mut(&mut self) -> &mut BackPack {
+            &mut self.robot.backpack
+        }
+    }
+
+    /// The `InMemoryBackend` struct is a [`DiscoveryBackend`] holding the whole world in memory,
+    /// to test the scan logic through [`ResourceScanner::scan_with_backend`] without a `Runner`.
+    ///
+    /// Like `discover_tiles`, discovering a tile costs 3 energy, while the tiles served as the
+    /// robot view are free. Every tile starts unknown, as in a freshly generated world.
+    ///
+    /// It is available with the `test_utils` feature. See
+    /// [`ResourceScanner::scan_with_backend`] for an example.
+    ///
+    /// [`ResourceScanner::scan_with_backend`]: crate::tool::resource_scanner::ResourceScanner::scan_with_backend
+    pub struct InMemoryBackend {
+        map: Vec<Vec<Tile>>,
+        known: Vec<Vec<Option<Tile>>>,
+        position: MapCoordinate,
+        energy: usize,
+    }
+
+    impl InMemoryBackend {
+        /// Creates a new `InMemoryBackend` for a robot at `position` in the given map, indexed
+        /// `[row][col]`, with 1000 energy.
+        pub fn new(map: Vec<Vec<Tile>>, position: MapCoordinate) -> Self {
+            let known = map.iter().map(|row| vec![None; row.len()]).collect();
+            Self {
+                map,
+                known,
+                position,
+                energy: 1000,
+            }
+        }
+
+        /// Creates a new `InMemoryBackend` for the world built by `generator`, placing the robot
+        /// at its spawn point.
+        pub fn from_generator(generator: &mut impl Generator) -> Self {
+            let (map, (row, col), ..) = generator.gen();
+            Self::new(map, MapCoordinate::new(col, row))
+        }
+
+        /// Sets the energy of the robot.
+        pub fn with_energy(mut self, energy: usize) -> Self {
+            self.energy = energy;
+            self
+        }
+
+        /// Marks the tile at column `x` and row `y` as already known to the robot.
+        pub fn with_known(mut self, x: usize, y: usize) -> Self {
+            self.known[y][x] = Some(self.map[y][x].clone());
+            self
+        }
+
+        /// Reveals the given coordinates into `tiles`, charging `cost` energy for each of them.
+        fn reveal(
+            &mut self,
+            coordinates: &[MapCoordinate],
+            tiles: &mut HashMap<(usize, usize), Option<Tile>>,
+            cost: usize,
+        ) -> Result<(), LibError> {
+            let size = self.map.len();
+            if coordinates
+                .iter()
+                .any(|c| c.get_col() >= size || c.get_row() >= size)
+            {
+                return Err(LibError::OutOfBounds);
+            }
+            if self.energy < cost * coordinates.len() {
+                return Err(LibError::NotEnoughEnergy);
+            }
+            self.energy -= cost * coordinates.len();
+            for coordinate in coordinates {
+                let tile = self.map[coordinate.row()][coordinate.col()].clone();
+                self.known[coordinate.row()][coordinate.col()] = Some(tile.clone());
+                tiles.insert(coordinate.as_col_row(), Some(tile));
+            }
+            Ok(())
+        }
+    }
+
+    impl DiscoveryBackend for InMemoryBackend {
+        fn discover(
+            &mut self,
+            coordinates: &[MapCoordinate],
+            tiles: &mut HashMap<(usize, usize), Option<Tile>>,
+        ) -> Result<(), LibError> {
+            self.reveal(coordinates, tiles, 3)
+        }
+
+        fn view(
+            &mut self,
+            coordinates: &[MapCoordinate],
+            tiles: &mut HashMap<(usize, usize), Option<Tile>>,
+        ) -> Result<(), LibError> {
+            self.reveal(coordinates, tiles, 0)
+        }
+
+        fn known_map(&self) -> Option<Vec<Vec<Option<Tile>>>> {
+            Some(self.known.clone())
+        }
+
+        fn position(&self) -> MapCoordinate {
+            self.position
+        }
+
+        fn energy_level(&self) -> usize {
+            self.energy
+        }
+    }
+}
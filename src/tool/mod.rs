@@ -1,21 +1,35 @@
 pub mod resource_scanner {
+    use crate::backend::discovery_backend::{DiscoveryBackend, RobotBackend};
+    use crate::coordinates::coordinate_set::CoordinateSet;
+    use crate::coordinates::coordinatelike::Coordinatelike;
     use crate::coordinates::map_coordinate::MapCoordinate;
-    use crate::errors::tool_errors::ToolError;
+    use crate::coordinates::relative_coordinate::RelativeCoordinate;
+    use crate::energy::energy_budget::EnergyBudget;
+    use crate::errors::tool_errors::{PatternParseError, ScanOutcome, ToolError};
     use crate::errors::tool_errors::ToolError::*;
-    use robotics_lib::interface::{discover_tiles, robot_map, robot_view, Tools};
+    use robotics_lib::interface::{discover_tiles, robot_map, Direction, Tools};
     use robotics_lib::runner::Runnable;
     use robotics_lib::utils::LibError;
-    use robotics_lib::world::tile::{Content, Tile};
+    use robotics_lib::world::tile::{Content, Tile, TileType};
     use robotics_lib::world::World;
-    use std::collections::HashMap;
-    use std::error::Error;
+    #[cfg(feature = "rayon")]
+    use rayon::prelude::*;
+    use std::cmp::Reverse;
+    use std::collections::{HashMap, HashSet, VecDeque};
+    use std::fmt::{Display, Formatter};
     use std::mem;
+    use std::ops::{Deref, RangeInclusive};
+    use std::str::FromStr;
+    use std::sync::mpsc::Sender;
+    #[cfg(feature = "tokio")]
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex, PoisonError};
     
 
     /// Represents different scanning patterns used in the resource scanner tool.
     ///
-    /// The `Pattern` enum is used to specify the scanning behavior, and each variant
-    /// includes a `usize` field indicating the range or size of the scan.
+    /// The `Pattern` enum is used to specify the scanning behavior, and each variant but `Single`
+    /// and `Custom` includes a `usize` field indicating the range or size of the scan.
     ///
     /// # Variants
     ///
@@ -30,27 +44,48 @@ pub mod resource_scanner {
     /// - `DiagonalLowerRight(usize)`: Scans diagonally in the lower-right direction with the specified distance.
     /// - `StraightStar(usize)`: Scans in a star pattern in all directions with the specified distance.
     /// - `DiagonalStar(usize)`: Scans in a star pattern diagonally in all directions with the specified distance.
+    /// - `AreaPerimeter(usize)`: Scans only the border of the square area of `Area(usize)`, covering
+    ///   4 * (size - 1) tiles.
+    /// - `Ring(usize)`: Scans the tiles at the given distance from the robot, that is the border
+    ///   of the square of side 2 * radius + 1 centered on the robot, covering 8 * radius tiles.
+    /// - `Single { dx, dy }`: Scans only the tile `dx` columns right and `dy` rows below the robot
+    ///   (negative offsets go left and up), or nothing if that tile is outside the world. Its size
+    ///   is 1 and it ignores `with_include_center`.
+    /// - `Custom(Vec<(i64, i64)>)`: Scans the tiles at the given `(dx, dy)` offsets from the
+    ///   robot, as for `Single`, skipping the repeated ones. Its size is the number of offsets and
+    ///   it ignores `with_include_center`, the robot tile is covered if `(0, 0)` is listed.
     ///
-    /// ASCII drawing for `StraightStar(2)`:
+    /// For the directional and diagonal variants the size is the number of tiles beyond the robot,
+    /// so `DirectionRight(2)` covers the two tiles to the right of the robot. The star variants are
+    /// made of four such arms and cover 4 * size tiles. The robot tile is covered too only if the
+    /// scanner is configured with `with_include_center(true)`.
     ///
-    /// ```plaintext
-    ///      *
-    ///      *
-    ///    **r**
-    ///      *
-    ///      *
+    /// ASCII drawing for `StraightStar(2)`, as returned by [`Pattern::to_ascii`]:
+    ///
+    /// ```
+    /// # use resource_scanner_tool::tool::resource_scanner::Pattern;
+    /// let drawing = "
+    ///   *
+    ///   *
+    /// **r**
+    ///   *
+    ///   *";
+    /// assert_eq!(Pattern::StraightStar(2).to_ascii(), drawing.trim_start_matches('\n'));
     /// ```
     ///
     /// ASCII drawing for `DiagonalStar(3)`:
     ///
-    /// ```plaintext
-    ///  *     *
-    ///   *   *
-    ///    * *
-    ///     r
-    ///    * *
-    ///   *   *
-    ///  *     *
+    /// ```
+    /// # use resource_scanner_tool::tool::resource_scanner::Pattern;
+    /// let drawing = "
+    /// *     *
+    ///  *   *
+    ///   * *
+    ///    r
+    ///   * *
+    ///  *   *
+    /// *     *";
+    /// assert_eq!(Pattern::DiagonalStar(3).to_ascii(), drawing.trim_start_matches('\n'));
     /// ```
     ///
     /// # Examples
@@ -62,7 +97,13 @@ pub mod resource_scanner {
     ///
     /// // Scan upward with a distance of 3.
     /// let up_scan = Pattern::DirectionUp(3);
+    ///
+    /// // Scan the tiles right of and below the robot.
+    /// let custom_scan = Pattern::Custom(vec![(1, 0), (0, 1)]);
     /// ```
+    ///
+    /// A pattern is `Clone` but not `Copy`, since the offsets of `Custom` are stored in a `Vec`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     pub enum Pattern {
         Area(usize),
         DirectionUp(usize),
@@ -75,211 +116,4583 @@ pub mod resource_scanner {
         DiagonalLowerRight(usize),
         StraightStar(usize),
         DiagonalStar(usize),
+        AreaPerimeter(usize),
+        Ring(usize),
+        Single { dx: i32, dy: i32 },
+        Custom(Vec<(i64, i64)>),
     }
 
+    /// The largest size drawn by [`Pattern::to_ascii`], odd so that it is a valid `Area` size.
+    pub const ASCII_MAX_SIZE: usize = 9;
+
     impl Pattern {
-        /// Checks if the given size is valid, that is if it is 0 or negative or if it is not
-        /// odd in the case of `Pattern::Area`
+        /// Checks if the given size is valid, that is if it is not 0, if it is odd and at least 3 in
+        /// the case of `Pattern::Area` and `Pattern::AreaPerimeter` and if it does not exceed the
+        /// size of the world, or, for `Pattern::Ring`, if the side of its square does not
+        /// exceed it.
+        /// # Returns
+        /// Returns `true` if the size is valid, `false` otherwise
+        fn check_size(&self, world_size: usize) -> bool {
+            return match self {
+                Pattern::Area(size) | Pattern::AreaPerimeter(size) if size % 2 == 0 || *size < 3 => false,
+                Pattern::Ring(radius) => *radius >= 1 && matches!(radius.checked_mul(2), Some(side) if side < world_size),
+                // the offsets are checked one by one when placed in the world
+                Pattern::Custom(offsets) => !offsets.is_empty(),
+                _ if self.size() < 1 => false,
+                // larger patterns cover no more tiles, and their size may not fit the coordinate math
+                _ => self.size() <= world_size,
+            };
+        }
+
+        /// Returns the size the pattern was built with.
+        fn size(&self) -> usize {
+            match self {
+                Pattern::Area(size)
+                | Pattern::DirectionUp(size)
+                | Pattern::DirectionRight(size)
+                | Pattern::DirectionLeft(size)
+                | Pattern::DirectionDown(size)
+                | Pattern::DiagonalUpperLeft(size)
+                | Pattern::DiagonalUpperRight(size)
+                | Pattern::DiagonalLowerLeft(size)
+                | Pattern::DiagonalLowerRight(size)
+                | Pattern::StraightStar(size)
+                | Pattern::DiagonalStar(size)
+                | Pattern::AreaPerimeter(size)
+                | Pattern::Ring(size) => *size,
+                Pattern::Single { .. } => 1,
+                Pattern::Custom(offsets) => offsets.len(),
+            }
+        }
+
+        /// Returns the `(x, y)` offsets from the robot of the tiles covered by the pattern, in
+        /// generation order, with `x` growing rightwards and `y` downwards.
+        ///
+        /// The robot tile is part of the directional, diagonal and star patterns only if
+        /// `include_center` is set. Returns `None` if the size does not fit the coordinate math.
+        fn offsets(&self, include_center: bool) -> Option<Vec<(i64, i64)>> {
+            let mut out = Vec::new();
+            let length = i64::try_from(self.size()).ok()?;
+            // the directional and diagonal patterns start from the robot tile if it is covered
+            let steps = i64::from(!include_center)..=length;
+            match self {
+                Pattern::Area(_) | Pattern::AreaPerimeter(_) => {
+                    let x_area_robot = length / 2;
+                    let y_area_robot = length / 2;
+                    let perimeter = matches!(self, Pattern::AreaPerimeter(_));
+                    for x in 0..length {
+                        for y in 0..length {
+                            // the perimeter keeps only the first and last rows and columns
+                            let border = x == 0 || y == 0 || x == length - 1 || y == length - 1;
+                            if perimeter && !border {
+                                continue;
+                            }
+                            // move from the area FoR to the robot FoR
+                            out.push((x - x_area_robot, y - y_area_robot));
+                        }
+                    }
+                }
+                Pattern::DirectionLeft(_) => out.extend(Self::arm((-1, 0), steps)),
+                Pattern::DirectionRight(_) => out.extend(Self::arm((1, 0), steps)),
+                Pattern::DirectionUp(_) => out.extend(Self::arm((0, -1), steps)),
+                Pattern::DirectionDown(_) => out.extend(Self::arm((0, 1), steps)),
+                Pattern::DiagonalUpperLeft(_) => out.extend(Self::arm((-1, -1), steps)),
+                Pattern::DiagonalUpperRight(_) => out.extend(Self::arm((1, -1), steps)),
+                Pattern::DiagonalLowerLeft(_) => out.extend(Self::arm((-1, 1), steps)),
+                Pattern::DiagonalLowerRight(_) => out.extend(Self::arm((1, 1), steps)),
+                Pattern::Single { dx, dy } => out.push((i64::from(*dx), i64::from(*dy))),
+                // the ring at radius r is the perimeter of the (2r + 1)-sided square
+                Pattern::Ring(radius) => {
+                    let side = radius.checked_mul(2)?.checked_add(1)?;
+                    return Pattern::AreaPerimeter(side).offsets(include_center);
+                }
+                Pattern::Custom(offsets) => {
+                    let mut seen = HashSet::new();
+                    out.extend(offsets.iter().filter(|&&offset| seen.insert(offset)));
+                }
+                Pattern::DiagonalStar(_) | Pattern::StraightStar(_) => {
+                    let arms = match self {
+                        Pattern::DiagonalStar(_) => [(1, 1), (1, -1), (-1, 1), (-1, -1)],
+                        _ => [(1, 0), (-1, 0), (0, 1), (0, -1)],
+                    };
+                    // the arms never cover the robot tile
+                    if include_center {
+                        out.push((0, 0));
+                    }
+                    for direction in arms {
+                        out.extend(Self::arm(direction, 1..=length));
+                    }
+                }
+            }
+            Some(out)
+        }
+
+        /// Returns the offsets of the tiles the given numbers of steps away from the robot along
+        /// `direction`, whose components are -1, 0 or 1.
+        fn arm((dx, dy): (i64, i64), steps: RangeInclusive<i64>) -> impl Iterator<Item = (i64, i64)> {
+            steps.map(move |i| (dx * i, dy * i))
+        }
+
+        /// Returns the same kind of pattern with another size, `Single` patterns are returned
+        /// unchanged and `Custom` patterns keep their first `size` offsets.
+        fn with_size(&self, size: usize) -> Pattern {
+            match self {
+                Pattern::Area(_) => Pattern::Area(size),
+                Pattern::DirectionUp(_) => Pattern::DirectionUp(size),
+                Pattern::DirectionRight(_) => Pattern::DirectionRight(size),
+                Pattern::DirectionLeft(_) => Pattern::DirectionLeft(size),
+                Pattern::DirectionDown(_) => Pattern::DirectionDown(size),
+                Pattern::DiagonalUpperLeft(_) => Pattern::DiagonalUpperLeft(size),
+                Pattern::DiagonalUpperRight(_) => Pattern::DiagonalUpperRight(size),
+                Pattern::DiagonalLowerLeft(_) => Pattern::DiagonalLowerLeft(size),
+                Pattern::DiagonalLowerRight(_) => Pattern::DiagonalLowerRight(size),
+                Pattern::StraightStar(_) => Pattern::StraightStar(size),
+                Pattern::DiagonalStar(_) => Pattern::DiagonalStar(size),
+                Pattern::AreaPerimeter(_) => Pattern::AreaPerimeter(size),
+                Pattern::Ring(_) => Pattern::Ring(size),
+                Pattern::Single { .. } => self.clone(),
+                Pattern::Custom(offsets) => Pattern::Custom(offsets.iter().take(size).copied().collect()),
+            }
+        }
+
+        /// Returns the pattern turned so that its up direction points to `heading`, for robots
+        /// expressing patterns relative to the direction they are facing.
+        ///
+        /// `DirectionUp` becomes the direction ahead, `DirectionLeft` and `DirectionRight` the ones
+        /// on the sides and `DirectionDown` the one behind, the diagonals and the `Single` and
+        /// `Custom` offsets turn with them. The symmetric patterns, `Area`, `AreaPerimeter`, `Ring`
+        /// and the stars, are returned unchanged.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::Pattern;
+        /// use robotics_lib::interface::Direction;
+        /// assert_eq!(Pattern::DirectionRight(3), Pattern::DirectionUp(3).oriented(Direction::Right));
+        /// assert_eq!(Pattern::DiagonalLowerLeft(2), Pattern::DiagonalUpperRight(2).oriented(Direction::Down));
+        /// assert_eq!(Pattern::Single { dx: 2, dy: -1 }, Pattern::Single { dx: 1, dy: 2 }.oriented(Direction::Left));
+        /// ```
+        pub fn oriented(&self, heading: Direction) -> Pattern {
+            let quarter_turns = match heading {
+                Direction::Up => 0,
+                Direction::Right => 1,
+                Direction::Down => 2,
+                Direction::Left => 3,
+            };
+            (0..quarter_turns).fold(self.clone(), |pattern, _| pattern.turned_clockwise())
+        }
+
+        /// Returns the pattern turned by a quarter clockwise, so that up becomes right.
+        fn turned_clockwise(&self) -> Pattern {
+            match *self {
+                Pattern::DirectionUp(size) => Pattern::DirectionRight(size),
+                Pattern::DirectionRight(size) => Pattern::DirectionDown(size),
+                Pattern::DirectionDown(size) => Pattern::DirectionLeft(size),
+                Pattern::DirectionLeft(size) => Pattern::DirectionUp(size),
+                Pattern::DiagonalUpperLeft(size) => Pattern::DiagonalUpperRight(size),
+                Pattern::DiagonalUpperRight(size) => Pattern::DiagonalLowerRight(size),
+                Pattern::DiagonalLowerRight(size) => Pattern::DiagonalLowerLeft(size),
+                Pattern::DiagonalLowerLeft(size) => Pattern::DiagonalUpperLeft(size),
+                // rows grow downwards, so (dx, dy) turns into (-dy, dx)
+                Pattern::Single { dx, dy } => Pattern::Single {
+                    dx: dy.saturating_neg(),
+                    dy: dx,
+                },
+                Pattern::Custom(ref offsets) => {
+                    Pattern::Custom(offsets.iter().map(|&(dx, dy)| (dy.saturating_neg(), dx)).collect())
+                }
+                Pattern::Area(_)
+                | Pattern::AreaPerimeter(_)
+                | Pattern::Ring(_)
+                | Pattern::StraightStar(_)
+                | Pattern::DiagonalStar(_) => self.clone(),
+            }
+        }
+
+        /// Draws the footprint of the pattern relative to the robot, with `r` for the robot and
+        /// `*` for the covered tiles, as in the drawings of the [`Pattern`] documentation.
+        ///
+        /// The drawing is computed from the same offsets used by the scans, trailing spaces are
+        /// removed and the lines are separated by `\n`. Patterns larger than
+        /// [`ASCII_MAX_SIZE`] are drawn with that size, followed by a `...` line, like `Single` and
+        /// `Custom` offsets farther than that, which are drawn at that distance, and rings whose
+        /// square is wider than that, which are drawn with the largest radius fitting it.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::Pattern;
+        /// assert_eq!(Pattern::DirectionRight(3).to_ascii(), "r***");
+        /// assert_eq!(Pattern::DiagonalUpperLeft(1).to_ascii(), "*\n r");
+        /// ```
+        pub fn to_ascii(&self) -> String {
+            let limit = ASCII_MAX_SIZE as i32;
+            let (truncated, pattern) = match self {
+                Pattern::Single { dx, dy } => (
+                    dx.unsigned_abs().max(dy.unsigned_abs()) > ASCII_MAX_SIZE as u32,
+                    Pattern::Single { dx: (*dx).clamp(-limit, limit), dy: (*dy).clamp(-limit, limit) },
+                ),
+                Pattern::Custom(offsets) => {
+                    let limit = i64::from(limit);
+                    (
+                        offsets.iter().any(|(dx, dy)| dx.unsigned_abs().max(dy.unsigned_abs()) > ASCII_MAX_SIZE as u64),
+                        Pattern::Custom(
+                            offsets
+                                .iter()
+                                .map(|&(dx, dy)| (dx.clamp(-limit, limit), dy.clamp(-limit, limit)))
+                                .collect(),
+                        ),
+                    )
+                }
+                Pattern::Ring(radius) => (*radius > ASCII_MAX_SIZE / 2, Pattern::Ring((*radius).min(ASCII_MAX_SIZE / 2))),
+                _ => (self.size() > ASCII_MAX_SIZE, self.with_size(self.size().min(ASCII_MAX_SIZE))),
+            };
+            let offsets = pattern.offsets(false).unwrap_or_default();
+            // the bounding box of the footprint and of the robot
+            let (mut min_x, mut max_x, mut min_y, mut max_y) = (0, 0, 0, 0);
+            for (x, y) in offsets.iter() {
+                min_x = min_x.min(*x);
+                max_x = max_x.max(*x);
+                min_y = min_y.min(*y);
+                max_y = max_y.max(*y);
+            }
+            let mut lines = Vec::new();
+            for y in min_y..=max_y {
+                let line: String = (min_x..=max_x)
+                    .map(|x| match (x, y) {
+                        (0, 0) => 'r',
+                        offset if offsets.contains(&offset) => '*',
+                        _ => ' ',
+                    })
+                    .collect();
+                lines.push(line.trim_end().to_string());
+            }
+            if truncated {
+                lines.push("...".to_string());
+            }
+            lines.join("\n")
+        }
+
+        /// Returns the number of tiles covered by the pattern when no tile falls outside the world.
+        #[cfg(feature = "logging")]
+        fn max_tiles(&self, include_center: bool) -> usize {
+            match self {
+                Pattern::Area(size) => size * size,
+                Pattern::DirectionUp(size)
+                | Pattern::DirectionRight(size)
+                | Pattern::DirectionLeft(size)
+                | Pattern::DirectionDown(size)
+                | Pattern::DiagonalUpperLeft(size)
+                | Pattern::DiagonalUpperRight(size)
+                | Pattern::DiagonalLowerLeft(size)
+                | Pattern::DiagonalLowerRight(size) => size + usize::from(include_center),
+                Pattern::StraightStar(size) | Pattern::DiagonalStar(size) => 4 * size + usize::from(include_center),
+                Pattern::AreaPerimeter(size) => 4 * (size - 1),
+                Pattern::Ring(radius) => 8 * radius,
+                Pattern::Single { .. } => 1,
+                Pattern::Custom(offsets) => offsets.iter().collect::<HashSet<_>>().len(),
+            }
+        }
+    }
+
+    /// Writes the pattern in the grammar read by its `FromStr` implementation, e.g. `area:5`,
+    /// `single:-2,1` or `custom:(1,0);(0,1)`, so that patterns round-trip through strings.
+    impl Display for Pattern {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            let name = match self {
+                Pattern::Area(_) => "area",
+                Pattern::DirectionUp(_) => "up",
+                Pattern::DirectionRight(_) => "right",
+                Pattern::DirectionLeft(_) => "left",
+                Pattern::DirectionDown(_) => "down",
+                Pattern::DiagonalUpperLeft(_) => "up-left",
+                Pattern::DiagonalUpperRight(_) => "up-right",
+                Pattern::DiagonalLowerLeft(_) => "down-left",
+                Pattern::DiagonalLowerRight(_) => "down-right",
+                Pattern::StraightStar(_) => "star",
+                Pattern::DiagonalStar(_) => "diag-star",
+                Pattern::AreaPerimeter(_) => "perimeter",
+                Pattern::Ring(_) => "ring",
+                Pattern::Single { dx, dy } => return write!(f, "single:{},{}", dx, dy),
+                Pattern::Custom(offsets) => {
+                    let offsets: Vec<String> = offsets.iter().map(|(dx, dy)| format!("({},{})", dx, dy)).collect();
+                    return write!(f, "custom:{}", offsets.join(";"));
+                }
+            };
+            write!(f, "{}:{}", name, self.size())
+        }
+    }
+
+    /// Parses a pattern written as `name:size`, e.g. from a configuration file or the command
+    /// line.
+    ///
+    /// The names are `area`, `up`, `right`, `left`, `down`, `up-left`, `up-right`, `down-left`,
+    /// `down-right`, `star`, `diag-star`, `perimeter` and `ring`, matched ignoring the case,
+    /// followed by the size of the pattern, the radius for `ring`. `Single` patterns are written
+    /// `single:dx,dy` with signed offsets, and `Custom` patterns list their offsets in parentheses,
+    /// separated by `;`, as in `custom:(1,0);(0,1)`. Whitespace around the parts is ignored. The
+    /// size is not validated against a world, a pattern such as `area:4` parses and is then
+    /// refused by the scan.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PatternParseError`] describing the input if the separator is missing, the name
+    /// is unknown or the size is not a number, or not a list of offsets for `custom`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::tool::resource_scanner::Pattern;
+    /// assert_eq!("area:5".parse::<Pattern>().unwrap(), Pattern::Area(5));
+    /// assert_eq!("Up-Left: 3".parse::<Pattern>().unwrap(), Pattern::DiagonalUpperLeft(3));
+    /// assert_eq!("single:-2,1".parse::<Pattern>().unwrap(), Pattern::Single { dx: -2, dy: 1 });
+    /// assert_eq!("ring:2".parse::<Pattern>().unwrap(), Pattern::Ring(2));
+    /// assert_eq!("custom:(1,0);(0,1)".parse::<Pattern>().unwrap(), Pattern::Custom(vec![(1, 0), (0, 1)]));
+    /// assert!("circle:3".parse::<Pattern>().is_err());
+    /// ```
+    impl FromStr for Pattern {
+        type Err = PatternParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (name, size) = s
+                .split_once(':')
+                .ok_or_else(|| PatternParseError::Malformed(s.to_string()))?;
+            let name = name.trim().to_ascii_lowercase();
+            let size = size.trim();
+            fn offset<T: FromStr>(pair: &str) -> Option<(T, T)> {
+                let (dx, dy) = pair.split_once(',')?;
+                Some((dx.trim().parse().ok()?, dy.trim().parse().ok()?))
+            }
+            match name.as_str() {
+                "single" => {
+                    return offset(size)
+                        .map(|(dx, dy)| Pattern::Single { dx, dy })
+                        .ok_or_else(|| PatternParseError::InvalidSize(size.to_string()));
+                }
+                "custom" => {
+                    return size
+                        .split(';')
+                        .map(|item| {
+                            let item = item.trim();
+                            item.strip_prefix('(')
+                                .and_then(|item| item.strip_suffix(')'))
+                                .and_then(offset)
+                                .ok_or_else(|| PatternParseError::InvalidSize(item.to_string()))
+                        })
+                        .collect::<Result<Vec<(i64, i64)>, _>>()
+                        .map(Pattern::Custom);
+                }
+                _ => {}
+            }
+            let pattern: fn(usize) -> Pattern = match name.as_str() {
+                "area" => Pattern::Area,
+                "up" => Pattern::DirectionUp,
+                "right" => Pattern::DirectionRight,
+                "left" => Pattern::DirectionLeft,
+                "down" => Pattern::DirectionDown,
+                "up-left" => Pattern::DiagonalUpperLeft,
+                "up-right" => Pattern::DiagonalUpperRight,
+                "down-left" => Pattern::DiagonalLowerLeft,
+                "down-right" => Pattern::DiagonalLowerRight,
+                "star" => Pattern::StraightStar,
+                "diag-star" => Pattern::DiagonalStar,
+                "perimeter" => Pattern::AreaPerimeter,
+                "ring" => Pattern::Ring,
+                _ => return Err(PatternParseError::UnknownName(name)),
+            };
+            size.parse::<usize>()
+                .map(pattern)
+                .map_err(|_| PatternParseError::InvalidSize(size.to_string()))
+        }
+    }
+
+    /// The `ScanResult` struct represents the tile selected by a scan.
+    ///
+    /// ## Fields
+    ///
+    /// - `coordinate`: The coordinate of the tile containing the requested content.
+    /// - `quantity`: The quantity of the requested content found on the tile.
+    /// - `elevation`: The elevation of the tile.
+    /// - `elevation_delta`: The elevation of the tile minus the elevation of the robot's tile, or
+    ///   `None` if the robot's own tile is not known.
+    /// - `walkable`: Whether the robot can walk on the tile, according to the properties of its
+    ///   `TileType`.
+    /// - `origin`: The position of the robot when it performed the scan.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct ScanResult {
+        pub coordinate: MapCoordinate,
+        pub quantity: usize,
+        pub elevation: usize,
+        pub elevation_delta: Option<isize>,
+        pub walkable: bool,
+        pub origin: MapCoordinate,
+    }
+
+    impl ScanResult {
+        /// Returns the offset of the tile from the position of the robot at scan time, as
+        /// `(columns, rows)`, e.g. `(1, -2)` for a tile one column right and two rows above.
+        pub fn offset(&self) -> (isize, isize) {
+            (
+                self.coordinate.get_col() as isize - self.origin.get_col() as isize,
+                self.coordinate.get_row() as isize - self.origin.get_row() as isize,
+            )
+        }
+
+        /// Returns the offset of the tile from `robot_pos`, as `(columns, rows)`, e.g. `(2, -1)`
+        /// for a tile two columns right and one row above, and `(0, 0)` for the robot tile.
+        ///
+        /// Unlike [`ScanResult::offset`] the reference position is given, e.g. the current
+        /// position of a robot which moved since the scan. Offsets beyond the `i32` range
+        /// saturate.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// use resource_scanner_tool::tool::resource_scanner::ScanResult;
+        /// let result = ScanResult {
+        ///     coordinate: MapCoordinate::new(7, 4),
+        ///     quantity: 1,
+        ///     elevation: 0,
+        ///     elevation_delta: None,
+        ///     walkable: true,
+        ///     origin: MapCoordinate::new(0, 0),
+        /// };
+        /// assert_eq!(result.relative_to(MapCoordinate::new(5, 5)), (2, -1));
+        /// ```
+        pub fn relative_to(&self, robot_pos: MapCoordinate) -> (i32, i32) {
+            let delta = |to: usize, from: usize| (to as i128 - from as i128).clamp(i32::MIN.into(), i32::MAX.into()) as i32;
+            (
+                delta(self.coordinate.get_col(), robot_pos.get_col()),
+                delta(self.coordinate.get_row(), robot_pos.get_row()),
+            )
+        }
+    }
+
+    /// The `ScanRequest` struct describes a scan together with its optional filters.
+    ///
+    /// A request is created with [`ScanRequest::new`] and configured with the `with_*` methods,
+    /// then passed to [`ResourceScanner::scan_request`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ScanRequest};
+    /// use robotics_lib::world::tile::Content;
+    /// // ignore the coins more than 2 elevation levels above or below the robot
+    /// let request = ScanRequest::new(Pattern::Area(5), Content::Coin(0)).with_elevation_range(0..=2);
+    /// ```
+    ///
+    /// A request is `Clone` but not `Copy`, since its pattern, content and elevation range may
+    /// hold a `Vec` or a `Range`. A clone draws from the same [`EnergyBudget`] as the original, if
+    /// any, and is equal to it.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ScanRequest {
+        pattern: Pattern,
+        content: Content,
+        elevation_range: Option<RangeInclusive<usize>>,
+        line_of_sight: bool,
+        walkable_only: bool,
+        max_energy: Option<usize>,
+        budget: Option<EnergyBudget>,
+        order: ResultOrder,
+        first_at_least: Option<usize>,
+        relative_coordinates: bool,
+    }
+
+    impl ScanRequest {
+        /// Creates a new `ScanRequest` for the given pattern and content, without filters.
+        pub fn new(pattern: Pattern, content: Content) -> Self {
+            Self {
+                pattern,
+                content,
+                elevation_range: None,
+                line_of_sight: false,
+                walkable_only: false,
+                max_energy: None,
+                budget: None,
+                order: ResultOrder::default(),
+                first_at_least: None,
+                relative_coordinates: false,
+            }
+        }
+
+        /// Keeps only the tiles whose elevation differs from the elevation of the robot's tile
+        /// by an amount within `elevation_range`.
+        ///
+        /// The difference is absolute, so `0..=2` keeps the tiles at most 2 levels above or below
+        /// the robot.
+        pub fn with_elevation_range(mut self, elevation_range: RangeInclusive<usize>) -> Self {
+            self.elevation_range = Some(elevation_range);
+            self
+        }
+
+        /// Keeps only the tiles in line of sight of the robot.
+        ///
+        /// A tile is in line of sight if no tile on the straight line between the robot and the
+        /// tile, computed with Bresenham's algorithm, is strictly higher than both of them.
+        /// Tiles on the line which are still unknown after the scan are treated as not blocking.
+        pub fn require_line_of_sight(mut self, line_of_sight: bool) -> Self {
+            self.line_of_sight = line_of_sight;
+            self
+        }
+
+        /// Keeps only the tiles the robot can walk on, according to the properties of their
+        /// `TileType`.
+        pub fn walkable_only(mut self, walkable_only: bool) -> Self {
+            self.walkable_only = walkable_only;
+            self
+        }
+
+        /// Fails the scan with `ToolError::EnergyCapExceeded` if discovering its tiles would cost
+        /// more than `max_energy`, overriding the cap of the scanner, if any.
+        pub fn max_energy(mut self, max_energy: usize) -> Self {
+            self.max_energy = Some(max_energy);
+            self
+        }
+
+        /// Draws the energy of the scan from the given budget, shared with other tools.
+        ///
+        /// The estimated cost of the scan is reserved before any tile is discovered, and the part
+        /// not actually spent is refunded afterwards. If the budget cannot cover the estimate, the
+        /// scan fails with `ToolError::BudgetExhausted` without discovering anything.
+        ///
+        /// The request keeps a handle to the same units as `budget`, see [`EnergyBudget`], so the
+        /// caller sees the reservation and the refund through its own handle.
+        pub fn budget(mut self, budget: &EnergyBudget) -> Self {
+            self.budget = Some(budget.clone());
+            self
+        }
+
+        /// Makes [`ResourceScanner::scan_request`] return the first tile holding at least
+        /// `quantity` of the content, instead of the tile with the highest quantity.
+        ///
+        /// The unknown tiles of the footprint are discovered one at a time, nearest to the robot
+        /// first, and the discovery stops as soon as a tile passing the filters holds enough
+        /// content, saving the energy of the remaining tiles. If no tile qualifies, the scan
+        /// returns `None`, even if tiles with less content were found. The energy cap and the
+        /// budget are checked against the cost of the whole footprint.
+        pub fn return_first_at_least(mut self, quantity: usize) -> Self {
+            self.first_at_least = Some(quantity);
+            self
+        }
+
+        /// Sets the order of the tiles returned by [`ResourceScanner::scan_request_all`] and
+        /// [`ResourceScanner::scan_best_n`], `ResultOrder::QuantityDesc` by default.
+        pub fn with_order(mut self, order: ResultOrder) -> Self {
+            self.order = order;
+            self
+        }
+
+        /// Makes [`ResourceScanner::scan_request_matches`] return the tiles as offsets from the
+        /// robot instead of absolute coordinates, like a scanner built
+        /// [`with_relative_coordinates`](ResourceScanner::with_relative_coordinates).
+        pub fn relative_coordinates(mut self, relative_coordinates: bool) -> Self {
+            self.relative_coordinates = relative_coordinates;
+            self
+        }
+    }
+
+    /// The `ResultOrder` enum defines the order of the tiles returned by the scans with several
+    /// results.
+    ///
+    /// Ties are always broken by coordinate, lowest row first and then lowest column, so the
+    /// order is deterministic.
+    ///
+    /// # Variants
+    ///
+    /// - `QuantityDesc`: The highest quantity first. This is the default, and the tile returned
+    ///   by [`ResourceScanner::scan`] is the head of this order.
+    /// - `DistanceAsc`: The nearest tile to the robot first, by Manhattan distance.
+    /// - `RowMajor`: By coordinate only.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum ResultOrder {
+        #[default]
+        QuantityDesc,
+        DistanceAsc,
+        RowMajor,
+    }
+
+    impl ResultOrder {
+        /// Sorts the results in this order, measuring the distances from their origin.
+        fn sort(&self, results: &mut [ScanResult]) {
+            let row_major = |result: &ScanResult| (result.coordinate.get_row(), result.coordinate.get_col());
+            match self {
+                ResultOrder::QuantityDesc => results.sort_by_key(|x| (Reverse(x.quantity), row_major(x))),
+                ResultOrder::DistanceAsc => {
+                    results.sort_by_key(|x| (x.coordinate.manhattan_distance(&x.origin), row_major(x)))
+                }
+                ResultOrder::RowMajor => results.sort_by_key(row_major),
+            }
+        }
+    }
+
+    /// The `ScanReport` struct collects statistics about a single scan.
+    ///
+    /// ## Fields
+    ///
+    /// - `tiles_requested`: The number of in-bounds tiles covered by the pattern.
+    /// - `tiles_known`: The number of those tiles that were already known before the scan.
+    /// - `tiles_discovered`: The number of tiles newly discovered by the scan.
+    /// - `energy_consumed`: The energy spent by the robot during the scan.
+    /// - `energy_estimated`: The energy the scan was expected to cost before the discovery, with
+    ///   the energy per tile of the scanner.
+    /// - `used_robot_view`: Whether the `robot_view` shortcut was used instead of `discover_tiles`.
+    /// - `discovered`: The tiles newly discovered by the scan, sorted by row and then by column.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct ScanReport {
+        pub tiles_requested: usize,
+        pub tiles_known: usize,
+        pub tiles_discovered: usize,
+        pub energy_consumed: usize,
+        pub energy_estimated: usize,
+        pub used_robot_view: bool,
+        pub discovered: Vec<DiscoveredTile>,
+    }
+
+    #[cfg(feature = "json")]
+    impl ScanReport {
+        /// Exports the discovered tiles as CSV, with a header line followed by one line per tile.
+        ///
+        /// The columns are `col,row,tile_type,content,elevation`, where the tile type and the
+        /// content are written as by their `Debug` implementation, e.g. `Grass` and `Coin(2)`.
+        ///
+        /// It is available with the `json` feature.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// use resource_scanner_tool::tool::resource_scanner::{DiscoveredTile, ScanReport};
+        /// use robotics_lib::world::tile::{Content, TileType};
+        /// let report = ScanReport {
+        ///     discovered: vec![DiscoveredTile {
+        ///         coordinate: MapCoordinate::new(4, 2),
+        ///         tile_type: TileType::Sand,
+        ///         content: Content::Rock(3),
+        ///         elevation: 1,
+        ///     }],
+        ///     ..ScanReport::default()
+        /// };
+        /// assert_eq!(report.to_csv(), "col,row,tile_type,content,elevation\n4,2,Sand,Rock(3),1\n");
+        /// ```
+        pub fn to_csv(&self) -> String {
+            let mut out = String::from("col,row,tile_type,content,elevation\n");
+            for tile in self.discovered.iter() {
+                out.push_str(&format!(
+                    "{},{},{:?},{:?},{}\n",
+                    tile.coordinate.get_col(),
+                    tile.coordinate.get_row(),
+                    tile.tile_type,
+                    tile.content,
+                    tile.elevation
+                ));
+            }
+            out
+        }
+    }
+
+    /// The `DiscoveredTile` struct describes a tile discovered by a scan, see [`ScanReport`].
+    ///
+    /// ## Fields
+    ///
+    /// - `coordinate`: The coordinate of the tile.
+    /// - `tile_type`: The type of the tile.
+    /// - `content`: The content of the tile.
+    /// - `elevation`: The elevation of the tile.
+    ///
+    /// It is not `Copy` because `Content::Bin`, `Content::Crate` and `Content::Bank` hold a
+    /// `Range`, which is not `Copy` either.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DiscoveredTile {
+        pub coordinate: MapCoordinate,
+        pub tile_type: TileType,
+        pub content: Content,
+        pub elevation: usize,
+    }
+
+    /// The `ScanPlan` struct describes the tiles a pattern covers around the robot.
+    ///
+    /// Coordinates follow the `MapCoordinate` convention used throughout the crate: `col` is the
+    /// column (x) and `row` is the row (y) of the tile in the world map.
+    ///
+    /// ## Fields
+    ///
+    /// - `footprint`: Every in-bounds coordinate covered by the pattern, in generation order.
+    /// - `sanitized`: The subset of `footprint` not yet known to the robot, in the same relative order.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ScanPlan {
+        pub footprint: Vec<MapCoordinate>,
+        pub sanitized: Vec<MapCoordinate>,
+    }
+
+    /// The `ScanDiff` struct describes how the matches of a region changed between two scans, as
+    /// computed by [`ResourceScanner::diff`].
+    ///
+    /// ## Fields
+    ///
+    /// - `added`: The matches of the current scan missing from the previous one, in the order of
+    ///   the current scan.
+    /// - `removed`: The matches of the previous scan missing from the current one, in the order of
+    ///   the previous scan.
+    /// - `changed`: The coordinates matched by both scans with a different quantity, as
+    ///   `(coordinate, previous quantity, current quantity)` in the order of the current scan.
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct ScanDiff {
+        pub added: Vec<(MapCoordinate, usize)>,
+        pub removed: Vec<(MapCoordinate, usize)>,
+        pub changed: Vec<(MapCoordinate, usize, usize)>,
+    }
+
+    impl ScanDiff {
+        /// Returns `true` if the two scans hold the same matches with the same quantities.
+        pub fn is_empty(&self) -> bool {
+            self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+        }
+    }
+
+    /// The `ScanSummary` struct describes the matches of a scan at once, as computed by
+    /// [`ResourceScanner::scan_summary`].
+    ///
+    /// ## Fields
+    ///
+    /// - `best`: The coordinate and quantity of the richest tile, selected like `scan` does.
+    /// - `total`: The sum of the quantities of the matching tiles.
+    /// - `count`: The number of matching tiles.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ScanSummary {
+        pub best: (MapCoordinate, usize),
+        pub total: usize,
+        pub count: usize,
+    }
+
+    /// The `ScanResults` struct holds every tile found by [`ResourceScanner::scan_all`] as
+    /// `(coordinate, quantity)` pairs.
+    ///
+    /// It dereferences to a slice, so the usual slice methods (`len`, `iter`, indexing, ...) are
+    /// available, and it can be iterated both by value and by reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+    /// use resource_scanner_tool::tool::resource_scanner::ScanResults;
+    /// let results = ScanResults::from(vec![
+    ///     (MapCoordinate::new(1, 2), 3),
+    ///     (MapCoordinate::new(4, 4), 5),
+    /// ]);
+    /// assert_eq!(results.len(), 2);
+    /// assert_eq!(results.best(), Some((MapCoordinate::new(4, 4), 5)));
+    /// assert_eq!(results.total(), 8);
+    /// ```
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct ScanResults(Vec<(MapCoordinate, usize)>);
+
+    impl ScanResults {
+        /// Returns the tile with the highest quantity, or `None` if no tile was found.
+        ///
+        /// Ties are broken as in [`ResourceScanner::scan`].
+        pub fn best(&self) -> Option<(MapCoordinate, usize)> {
+            self.0.iter().max_by_key(|x| selection_key(&x.0, x.1)).cloned()
+        }
+
+        /// Returns the tile closest to the robot according to the Manhattan distance, or `None`
+        /// if no tile was found.
+        ///
+        /// Ties are broken by coordinate order: lowest row first, then lowest column.
+        pub fn nearest(&self, robot: &impl Runnable) -> Option<(MapCoordinate, usize)> {
+            let x_robot = robot.get_coordinate().get_col();
+            let y_robot = robot.get_coordinate().get_row();
+            self.0
+                .iter()
+                .min_by_key(|(coordinate, _)| {
+                    (
+                        coordinate.get_col().abs_diff(x_robot) + coordinate.get_row().abs_diff(y_robot),
+                        coordinate.get_row(),
+                        coordinate.get_col(),
+                    )
+                })
+                .cloned()
+        }
+
+        /// Returns the sum of the quantities of all the tiles.
+        pub fn total(&self) -> usize {
+            self.0.iter().map(|x| x.1).sum()
+        }
+    }
+
+    impl From<Vec<(MapCoordinate, usize)>> for ScanResults {
+        fn from(results: Vec<(MapCoordinate, usize)>) -> Self {
+            Self(results)
+        }
+    }
+
+    impl Deref for ScanResults {
+        type Target = [(MapCoordinate, usize)];
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl IntoIterator for ScanResults {
+        type Item = (MapCoordinate, usize);
+        type IntoIter = std::vec::IntoIter<(MapCoordinate, usize)>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.0.into_iter()
+        }
+    }
+
+    impl<'a> IntoIterator for &'a ScanResults {
+        type Item = &'a (MapCoordinate, usize);
+        type IntoIter = std::slice::Iter<'a, (MapCoordinate, usize)>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.0.iter()
+        }
+    }
+
+    /// The `ElevationPreference` enum selects the matching tile returned by
+    /// [`ResourceScanner::scan_by_elevation`].
+    ///
+    /// # Variants
+    ///
+    /// - `Lowest`: The tile with the lowest elevation, e.g. where water would flow.
+    /// - `Highest`: The tile with the highest elevation.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ElevationPreference {
+        Lowest,
+        Highest,
+    }
+
+    /// The `MatchCoordinate` enum holds the coordinate of a tile returned by
+    /// [`ResourceScanner::scan_matches`], in the frame chosen with
+    /// [`ResourceScanner::with_relative_coordinates`].
+    ///
+    /// # Variants
+    ///
+    /// - `Absolute`: The coordinate of the tile in the world map. This is the default.
+    /// - `Relative`: The offset of the tile from the position of the robot at scan time.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MatchCoordinate {
+        Absolute(MapCoordinate),
+        Relative(RelativeCoordinate),
+    }
+
+    /// The `ContentCategory` enum groups the contents searched by
+    /// [`ResourceScanner::scan_category`].
+    ///
+    /// # Variants
+    ///
+    /// - `Harvestable`: `Rock`, `Tree`, `Coin`, `Fish`, `Bush` and `Water`, the contents a robot
+    ///   collects.
+    /// - `Hazard`: `Fire` and `Garbage`, the contents a robot has to clear.
+    /// - `Storage`: `Bin`, `Crate` and `Bank`, the contents a robot puts things into.
+    /// - `Structure`: `Market`, `Building`, `Scarecrow` and `JollyBlock`.
+    ///
+    /// `Content::None` belongs to no category.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ContentCategory {
+        Harvestable,
+        Hazard,
+        Storage,
+        Structure,
+    }
+
+    impl ContentCategory {
+        /// Returns the category of the content, or `None` for `Content::None`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::ContentCategory;
+        /// use robotics_lib::world::tile::Content;
+        /// assert_eq!(ContentCategory::of(&Content::Fire), Some(ContentCategory::Hazard));
+        /// assert_eq!(ContentCategory::of(&Content::Bank(0..3)), Some(ContentCategory::Storage));
+        /// assert_eq!(ContentCategory::of(&Content::None), None);
+        /// ```
+        pub fn of(content: &Content) -> Option<ContentCategory> {
+            match content {
+                Content::Rock(_)
+                | Content::Tree(_)
+                | Content::Coin(_)
+                | Content::Fish(_)
+                | Content::Bush(_)
+                | Content::Water(_) => Some(ContentCategory::Harvestable),
+                Content::Fire | Content::Garbage(_) => Some(ContentCategory::Hazard),
+                Content::Bin(_) | Content::Crate(_) | Content::Bank(_) => Some(ContentCategory::Storage),
+                Content::Market(_) | Content::Building | Content::Scarecrow | Content::JollyBlock(_) => {
+                    Some(ContentCategory::Structure)
+                }
+                Content::None => None,
+            }
+        }
+
+        /// Returns `true` if the content belongs to the category.
+        pub fn contains(&self, content: &Content) -> bool {
+            ContentCategory::of(content) == Some(*self)
+        }
+    }
+
+    /// The `BoundaryPolicy` enum defines how the pattern coordinates falling outside the world are
+    /// handled.
+    ///
+    /// # Variants
+    ///
+    /// - `Clip`: The coordinates are omitted. This is the default.
+    /// - `Wrap`: The coordinates wrap around the world edges, as in a toroidal world. A coordinate
+    ///   reached more than once, e.g. when the arms of a star meet on the other side of the world,
+    ///   is scanned once.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum BoundaryPolicy {
+        #[default]
+        Clip,
+        Wrap,
+    }
+
+    impl BoundaryPolicy {
+        /// Places the tile at `offset` from `origin` according to the policy, returning `None` if
+        /// it is clipped.
+        fn apply<C: Coordinatelike>(&self, origin: MapCoordinate, (dx, dy): (i64, i64), world_size: usize) -> Option<C> {
+            let coordinate = match self {
+                BoundaryPolicy::Clip => origin.try_offset(dx, dy, (world_size, world_size))?,
+                BoundaryPolicy::Wrap => {
+                    let size = i64::try_from(world_size).ok().filter(|&size| size > 0)?;
+                    let x = i64::try_from(origin.x()).ok()?.checked_add(dx)?;
+                    let y = i64::try_from(origin.y()).ok()?.checked_add(dy)?;
+                    // rem_euclid is never negative, the casts cannot wrap
+                    MapCoordinate::new(x.rem_euclid(size) as usize, y.rem_euclid(size) as usize)
+                }
+            };
+            Some(C::from_col_row(coordinate.get_col(), coordinate.get_row()))
+        }
+    }
+
+    /// The `Query` enum represents a question answered by [`ResourceScanner::scan_batch`].
+    ///
+    /// # Variants
+    ///
+    /// - `ContentMax(Content)`: The tile holding the highest quantity of the content.
+    /// - `ContentNearest(Content)`: The tile holding the content closest to the robot, according to
+    ///   the Manhattan distance.
+    /// - `TileType(TileType)`: Every tile of the given type.
+    /// - `Hazards`: Every tile on fire, or whose type is `Lava` or `DeepWater`.
+    ///
+    /// Like `Content`, a query can be cloned but not copied.
+    #[derive(Debug, Clone)]
+    pub enum Query {
+        ContentMax(Content),
+        ContentNearest(Content),
+        TileType(TileType),
+        Hazards,
+    }
+
+    /// The `QueryResult` enum holds the answer to a [`Query`], with the variant of the same name.
+    ///
+    /// Coordinates are listed in the generation order of the pattern.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum QueryResult {
+        ContentMax(Option<(MapCoordinate, usize)>),
+        ContentNearest(Option<(MapCoordinate, usize)>),
+        TileType(Vec<MapCoordinate>),
+        Hazards(Vec<MapCoordinate>),
+    }
+
+    /// The energy the `discover_tiles` interface consumes for each discovered tile.
+    ///
+    /// It is the default energy per tile of the scanner, see
+    /// [`ResourceScanner::with_energy_per_tile`].
+    pub const DEFAULT_ENERGY_PER_TILE: usize = 3;
+
+    /// The number of tiles discovered between two yields of [`ResourceScanner::scan_async`].
+    #[cfg(feature = "tokio")]
+    pub const SCAN_BATCH_SIZE: usize = 64;
+
+    /// The number of patterns whose offsets a scanner keeps, see
+    /// [`ResourceScanner::offset_table`]. The table is emptied when it is full.
+    const OFFSET_TABLE_CAPACITY: usize = 16;
+
+    /// Returns the key used to select the best tile among the matching ones.
+    ///
+    /// The tile with the highest quantity wins. Ties are broken by coordinate order, preferring the
+    /// lowest row and then the lowest column, so the selection does not depend on the iteration
+    /// order of the discovered tiles.
+    fn selection_key(coordinate: &MapCoordinate, quantity: usize) -> (usize, Reverse<usize>, Reverse<usize>) {
+        (quantity, Reverse(coordinate.get_row()), Reverse(coordinate.get_col()))
+    }
+
+    /// The `ResourceScanner` tool scans the area around a robot for a given content.
+    ///
+    /// A scanner is created with [`ResourceScanner::new`] and configured with the `with_*`
+    /// methods, which consume and return the scanner.
+    ///
+    /// ## Cooldown
+    ///
+    /// A scanner configured with [`ResourceScanner::with_cooldown_ticks`] refuses to scan again
+    /// until the given number of ticks has elapsed since the previous successful scan, a failed
+    /// scan can be retried at once. The scanner has no access to the game clock, so the tick
+    /// counter must be driven by the user by calling [`ResourceScanner::tick`] once per game
+    /// tick, e.g. at the beginning of `process_tick`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+    /// let mut scanner = ResourceScanner::new().with_cooldown_ticks(3);
+    ///
+    /// // advance the tick counter once per game tick
+    /// scanner.tick();
+    /// ```
+    #[derive(Default)]
+    pub struct ResourceScanner {
+        boundary_policy: BoundaryPolicy,
+        strict_bounds: bool,
+        bounds: Option<(MapCoordinate, MapCoordinate)>,
+        include_center: bool,
+        force_discover_tiles: bool,
+        relative_coordinates: bool,
+        max_energy: Option<usize>,
+        energy_per_tile: Option<usize>,
+        cooldown_ticks: usize,
+        current_tick: usize,
+        last_scan_tick: Option<usize>,
+        last_clipped_count: usize,
+        last_pattern: Option<Pattern>,
+        last_result: Option<(MapCoordinate, usize)>,
+        cache: HashMap<(usize, usize), CacheEntry>,
+        discovered: CoordinateSet,
+        total_energy_consumed: usize,
+        reported: CoordinateSet,
+        offset_tables: Mutex<HashMap<(Pattern, bool), Arc<[(i64, i64)]>>>,
+        coordinate_buffer: Vec<MapCoordinate>,
+        row_col_keys: Vec<(usize, usize)>,
+    }
+
+    impl Tools for ResourceScanner {}
+
+    /// A content seen by the scanner, with the number of ticks elapsed since it was seen.
+    #[derive(Debug, Clone)]
+    struct CacheEntry {
+        content: Content,
+        age: usize,
+    }
+
+    impl ResourceScanner {
+        /// Creates a new `ResourceScanner` with the default configuration.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// let scanner = ResourceScanner::new();
+        /// ```
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Sets the number of ticks that must elapse between two scans.
+        ///
+        /// # Arguments
+        ///
+        /// * `cooldown_ticks` - The number of ticks, 0 disables the cooldown.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// let scanner = ResourceScanner::new().with_cooldown_ticks(2);
+        /// ```
+        pub fn with_cooldown_ticks(mut self, cooldown_ticks: usize) -> Self {
+            self.cooldown_ticks = cooldown_ticks;
+            self
+        }
+
+        /// Sets how the pattern coordinates falling outside the world are handled.
+        ///
+        /// # Arguments
+        ///
+        /// * `boundary_policy` - The policy, `BoundaryPolicy::Clip` by default.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::{BoundaryPolicy, ResourceScanner};
+        /// let scanner = ResourceScanner::new().with_boundary_policy(BoundaryPolicy::Wrap);
+        /// ```
+        pub fn with_boundary_policy(mut self, boundary_policy: BoundaryPolicy) -> Self {
+            self.boundary_policy = boundary_policy;
+            self
+        }
+
+        /// Sets whether a pattern reaching outside the world is an error instead of being clipped,
+        /// for callers considering an off-map scan a programming error.
+        ///
+        /// With strict bounds, the scans fail with `ToolError::Other("pattern out of bounds")`
+        /// before discovering any tile if a tile of the pattern falls outside the world. Wrapped
+        /// patterns never fall outside the world, so the flag only affects
+        /// `BoundaryPolicy::Clip`.
+        ///
+        /// # Arguments
+        ///
+        /// * `strict_bounds` - `true` to fail instead of clipping, `false` by default.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// let scanner = ResourceScanner::new().with_strict_bounds(true);
+        /// ```
+        pub fn with_strict_bounds(mut self, strict_bounds: bool) -> Self {
+            self.strict_bounds = strict_bounds;
+            self
+        }
+
+        /// Restricts the scans to a rectangle of the world, e.g. the fenced area a robot is
+        /// confined to.
+        ///
+        /// Pattern coordinates outside the rectangle are omitted like the ones outside the world,
+        /// after the boundary policy has been applied, but only the latter count as clipped in
+        /// [`ResourceScanner::last_clipped_count`].
+        ///
+        /// # Arguments
+        ///
+        /// * `top_left` - The corner with the lowest column and row, inclusive.
+        /// * `bottom_right` - The corner with the highest column and row, inclusive.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// // scan only the 10x10 square in the upper-left corner of the world
+        /// let scanner = ResourceScanner::new().with_bounds(MapCoordinate::new(0, 0), MapCoordinate::new(9, 9));
+        /// ```
+        pub fn with_bounds(mut self, top_left: MapCoordinate, bottom_right: MapCoordinate) -> Self {
+            self.bounds = Some((top_left, bottom_right));
+            self
+        }
+
+        /// Sets whether the directional, diagonal and star patterns also cover the robot tile.
+        ///
+        /// # Arguments
+        ///
+        /// * `include_center` - `true` to add the robot tile to the `size` tiles beyond the robot,
+        ///   `false` by default.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// let scanner = ResourceScanner::new().with_include_center(true);
+        /// ```
+        pub fn with_include_center(mut self, include_center: bool) -> Self {
+            self.include_center = include_center;
+            self
+        }
+
+        /// Sets whether `Area(3)` scans are served through `discover_tiles` instead of the
+        /// `robot_view` shortcut.
+        ///
+        /// The shortcut makes `Area(3)` free, but it is the only scan not charging the energy per
+        /// tile (see [`ResourceScanner::with_energy_per_tile`]) for each discovered tile. Forcing
+        /// `discover_tiles` costs up to 9 tiles per `Area(3)` scan, 27 energy with
+        /// [`DEFAULT_ENERGY_PER_TILE`], in exchange for a uniform and predictable cost across all
+        /// the patterns.
+        ///
+        /// # Arguments
+        ///
+        /// * `force_discover_tiles` - `true` to always use `discover_tiles`, `false` by default.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// let scanner = ResourceScanner::new().with_force_discover_tiles(true);
+        /// ```
+        pub fn with_force_discover_tiles(mut self, force_discover_tiles: bool) -> Self {
+            self.force_discover_tiles = force_discover_tiles;
+            self
+        }
+
+        /// Sets whether [`ResourceScanner::scan_matches`] reports the matching tiles as offsets
+        /// from the robot instead of absolute world coordinates.
+        ///
+        /// The offsets follow the [`RelativeCoordinate`] sign convention: a negative column is
+        /// left of the robot and a negative row is above it.
+        ///
+        /// # Arguments
+        ///
+        /// * `relative_coordinates` - `true` for offsets, `false` by default.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// let scanner = ResourceScanner::new().with_relative_coordinates(true);
+        /// ```
+        pub fn with_relative_coordinates(mut self, relative_coordinates: bool) -> Self {
+            self.relative_coordinates = relative_coordinates;
+            self
+        }
+
+        /// Caps the energy a single scan may consume.
+        ///
+        /// The cap is checked before any tile is discovered, against the estimated cost of the
+        /// scan: the energy per tile of [`ResourceScanner::with_energy_per_tile`] for each tile of
+        /// the footprint not yet known to the robot, or nothing when the `robot_view` shortcut is
+        /// used. A scan over the cap fails with
+        /// `ToolError::EnergyCapExceeded`, except [`ResourceScanner::scan_partial`], which
+        /// discovers only the tiles nearest to the robot that fit within it.
+        ///
+        /// The scans of explicit tiles are capped too: [`ResourceScanner::scan_coordinates`] and
+        /// [`ResourceScanner::scan_tile`] against the unknown tiles they are given, and
+        /// [`ResourceScanner::scan_flood`] against its `max_tiles`, which may all be unknown.
+        ///
+        /// # Arguments
+        ///
+        /// * `max_energy` - The maximum energy a scan may consume, unlimited by default.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// // never spend more than 200 energy on a single scan
+        /// let scanner = ResourceScanner::new().with_max_energy(200);
+        /// ```
+        pub fn with_max_energy(mut self, max_energy: usize) -> Self {
+            self.max_energy = Some(max_energy);
+            self
+        }
+
+        /// Sets the energy each discovered tile is expected to cost, for the `robotics_lib`
+        /// versions tuned differently from [`DEFAULT_ENERGY_PER_TILE`].
+        ///
+        /// The value is used by every estimate of the scanner: the energy checks before a scan,
+        /// the cap of [`ResourceScanner::with_max_energy`], the reservations from an
+        /// `EnergyBudget` and the `energy_estimated` of a `ScanReport`. It never changes which
+        /// tiles a pattern covers, and the energy actually consumed is still decided by
+        /// `robotics_lib`.
+        ///
+        /// # Arguments
+        ///
+        /// * `energy_per_tile` - The energy per discovered tile, `DEFAULT_ENERGY_PER_TILE` by
+        ///   default.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// let scanner = ResourceScanner::new().with_energy_per_tile(4);
+        /// ```
+        pub fn with_energy_per_tile(mut self, energy_per_tile: usize) -> Self {
+            self.energy_per_tile = Some(energy_per_tile);
+            self
+        }
+
+        /// Returns the energy each discovered tile is expected to cost.
+        fn energy_per_tile(&self) -> usize {
+            self.energy_per_tile.unwrap_or(DEFAULT_ENERGY_PER_TILE)
+        }
+
+        /// Fails with `ToolError::EnergyCapExceeded` if discovering `tiles` tiles would cost more
+        /// than the cap of the scanner, if any.
+        fn check_energy_cap(&self, tiles: usize) -> ScanOutcome<()> {
+            let estimated = self.energy_per_tile() * tiles;
+            match self.max_energy {
+                Some(cap) if estimated > cap => Err(EnergyCapExceeded { estimated, cap }),
+                _ => Ok(()),
+            }
+        }
+
+        /// Advances the tick counter of the scanner by one, aging the contents it remembers.
+        ///
+        /// It should be called once per game tick for the cooldown and the staleness of the
+        /// cache to be measured correctly.
+        pub fn tick(&mut self) {
+            self.current_tick += 1;
+            for entry in self.cache.values_mut() {
+                entry.age += 1;
+            }
+        }
+
+        /// Returns the number of pattern coordinates that fell outside the world during the last
+        /// scan and were omitted by the boundary policy.
+        ///
+        /// The coordinates left out by [`ResourceScanner::with_bounds`] and the ones covered twice
+        /// by a wrapped pattern are not counted, as they are not cut by the map edge.
+        ///
+        /// A non-zero value means the scan was clipped by the map edge, which may be a reason to
+        /// reposition the robot.
+        pub fn last_clipped_count(&self) -> usize {
+            self.last_clipped_count
+        }
+
+        /// Returns the tile selected by the last [`ResourceScanner::scan`] or
+        /// [`ResourceScanner::rescan`], if any.
+        pub fn last_result(&self) -> Option<(MapCoordinate, usize)> {
+            self.last_result
+        }
+
+        /// Returns the tiles containing the given content among the tiles discovered by the
+        /// scanner so far, without consuming energy.
+        ///
+        /// The scanner remembers the content of every tile discovered by its scans. The
+        /// contents are matched by variant, as in [`ResourceScanner::scan`], and the hits are
+        /// sorted by row, then by column.
+        ///
+        /// # Returns
+        ///
+        /// Returns the coordinates of the matching tiles along with the quantity of content.
+        pub fn cached_find(&self, content: &Content) -> Vec<(MapCoordinate, usize)> {
+            self.cached_entries()
+                .filter(|(_, entry)| mem::discriminant(&entry.content) == mem::discriminant(content))
+                .map(|(coordinate, entry)| (coordinate, ResourceScanner::content_quantity(&entry.content)))
+                .collect()
+        }
+
+        /// Returns the sightings of the cache older than `max_age` ticks, whose content may have
+        /// changed since, e.g. because a fire spread or a coin was collected.
+        ///
+        /// The age of a sighting is the number of calls to [`ResourceScanner::tick`] since its
+        /// tile was last seen by a scan, and is reset whenever a scan covers the tile again.
+        ///
+        /// # Returns
+        ///
+        /// Returns the coordinate, the content and the age of each stale sighting, sorted by row,
+        /// then by column.
+        pub fn stale_entries(&self, max_age: usize) -> Vec<(MapCoordinate, Content, usize)> {
+            self.cached_entries()
+                .filter(|(_, entry)| entry.age > max_age)
+                .map(|(coordinate, entry)| (coordinate, entry.content.clone(), entry.age))
+                .collect()
+        }
+
+        /// Proposes the scans refreshing the sightings older than `max_age` ticks.
+        ///
+        /// Each proposal is an `Area(3)` centered on a stale sighting, to be scanned once the robot
+        /// stands on the center, e.g. with [`ResourceScanner::scan_batch`], which reads the known
+        /// tiles too. A sighting next to the center of a previous proposal is covered by it, so the
+        /// stale sightings are walked by row, then by column, and a new area is proposed only for
+        /// the ones not covered yet.
+        ///
+        /// # Returns
+        ///
+        /// Returns the center and the pattern of each proposed scan.
+        pub fn suggest_rescan(&self, max_age: usize) -> Vec<(MapCoordinate, Pattern)> {
+            let mut proposals: Vec<(MapCoordinate, Pattern)> = Vec::new();
+            for (coordinate, _, _) in self.stale_entries(max_age) {
+                if !proposals.iter().any(|(center, _)| center.chebyshev_distance(&coordinate) <= 1) {
+                    proposals.push((coordinate, Pattern::Area(3)));
+                }
+            }
+            proposals
+        }
+
+        /// Returns the tiles whose content the scanner remembers, i.e. the tiles seen by its
+        /// scans or restored from a saved cache.
+        pub fn discovered_tiles(&self) -> &CoordinateSet {
+            &self.discovered
+        }
+
+        /// Returns the sightings of the cache by row, then by column, following the order of
+        /// the discovered tiles.
+        fn cached_entries(&self) -> impl Iterator<Item = (MapCoordinate, &CacheEntry)> + '_ {
+            self.discovered
+                .iter()
+                .map(|coordinate| (coordinate, &self.cache[&coordinate.as_col_row()]))
+        }
+
+        /// Remembers the content seen on a tile by a scan, resetting its age.
+        fn record_sighting(&mut self, coordinate: MapCoordinate, content: &Content) {
+            self.discovered.insert(coordinate);
+            self.cache.insert(
+                coordinate.as_col_row(),
+                CacheEntry {
+                    content: content.clone(),
+                    age: 0,
+                },
+            );
+        }
+
+        /// Returns the energy consumed by all the scans of the scanner, including the ones
+        /// discovering single tiles such as [`ResourceScanner::scan_tile`].
+        pub fn total_energy_consumed(&self) -> usize {
+            self.total_energy_consumed
+        }
+
+        /// Checks whether the cooldown has elapsed since the last successful scan.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::Other` if the scanner is still cooling down.
+        fn check_cooldown(&self) -> ScanOutcome<()> {
+            if let Some(last_scan_tick) = self.last_scan_tick {
+                if self.current_tick - last_scan_tick < self.cooldown_ticks {
+                    #[cfg(feature = "logging")]
+                    log::debug!("scan refused: cooling down since tick {}", last_scan_tick);
+                    return Err(ToolError::Other("scanner cooling down".to_string()));
+                }
+            }
+            Ok(())
+        }
+
+        /// Records the current tick as the tick of the last scan, starting the cooldown.
+        ///
+        /// Only the scans which succeed are recorded, so that a scan refused for lack of energy,
+        /// or failing any other check, can be retried at once.
+        fn record_scan(&mut self) {
+            #[cfg(feature = "logging")]
+            log::debug!("scan recorded at tick {}", self.current_tick);
+            self.last_scan_tick = Some(self.current_tick);
+        }
+
+        /// The scan function scans an area around the robot for the required content according to the pattern.
+
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// ## Notes on Content Behavior
+        ///
+        /// The `Content` enum can have different associated types, the scan tool is designed to operate seamlessly for `usize` and `()`.
+        /// The contents `Content::Bin(Range<usize>)`, `Content::Crate(Range<usize>)` and `Content::Bank(Range<usize>)` are
+        /// matched like any other content, and their quantity is the capacity of the range, computed as `range.end - range.start`.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing either:
+        /// - `Some((coordinates, count))`: If content is found, where `coordinates` is the location and `count` is the number of occurrences.
+        ///   The coordinates follow the convention of [`MapCoordinate`]: `x` is the column and `y`
+        ///   the row, so the tile is `robot_map(world)[coordinates.row()][coordinates.col()]`.
+        /// - `None`: If no content is found.
+        /// - `Err`: If the robot doesn't have enough energy to perform the scan.
+        ///
+        /// Only tiles that were not known to the robot before the scan are considered, including
+        /// when `Area(3)` is served through `robot_view`.
+        ///
+        /// When several tiles share the highest quantity, the one with the lowest row is returned,
+        /// and among those the one with the lowest column, so the result is deterministic. The
+        /// result is therefore the first tile of the `ResultOrder::QuantityDesc` order, see
+        /// [`ResourceScanner::scan_request_all`].
+        ///
+        /// # Energy Cost
+        ///
+        /// This tool uses the underlying interface `discover_tile` to discover tiles. Since it
+        /// consumes energy for each discovered tile, the scan function first checks if enough
+        /// energy is present to complete the task.
+        /// The following are the different energy costs based on pattern and size, where `e` is
+        /// the energy per tile of [`ResourceScanner::with_energy_per_tile`],
+        /// [`DEFAULT_ENERGY_PER_TILE`] (3) by default, and assuming no tiles have already been
+        /// discovered:
+        ///
+        /// - `Area(size)`: free if size = 3 (unless the scanner forces `discover_tiles`, see
+        ///   [`ResourceScanner::with_force_discover_tiles`]), else e * size * size
+        /// - `DirectionUp(size)`: e * size
+        /// - `DirectionRight(size)`: e * size
+        /// - `DirectionLeft(size)`: e * size
+        /// - `DirectionDown(size)`: e * size
+        /// - `DiagonalUpperLeft(size)`: e * size
+        /// - `DiagonalUpperRight(size)`: e * size
+        /// - `DiagonalLowerLeft(size)`: e * size
+        /// - `DiagonalLowerRight(size)`: e * size
+        /// - `StraightStar(size)`: e * 4 * size
+        /// - `DiagonalStar(size)`: e * 4 * size
+        /// - `AreaPerimeter(size)`: e * 4 * (size - 1)
+        /// - `Ring(radius)`: e * 8 * radius
+        /// - `Single { dx, dy }`: e, or nothing if the tile is outside the world
+        /// - `Custom(offsets)`: e for each distinct offset inside the world
+        ///
+        /// The directional, diagonal and star costs grow by e when the scanner includes the center
+        /// and the robot tile is not known yet, the other patterns ignore
+        /// [`ResourceScanner::with_include_center`].
+        ///
+        pub fn scan(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            let (result, _) = self.scan_with_report(world, robot, pattern, content)?;
+            Ok(result.map(|r| (r.coordinate, r.quantity)))
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan`], returning the selected tile as a
+        /// [`ScanResult`], which also records the position of the robot at scan time.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the selected `ScanResult`, if any, or an `Err` under the
+        /// same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if let Some(result) = scanner.scan_detailed(world, robot, &Pattern::Area(5), &Content::Coin(0))? {
+        ///     println!("found from {:?}, at offset {:?}", result.origin, result.offset());
+        /// }
+        /// ```
+        pub fn scan_detailed(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<ScanResult>> {
+            let (result, _) = self.scan_with_report(world, robot, pattern, content)?;
+            Ok(result)
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan`], returning the coordinate of the
+        /// selected tile as the caller's own coordinate type, see [`Coordinatelike`].
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinate and the quantity of the selected tile, if
+        /// any, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let found: Option<(EngineCell, usize)> =
+        ///     scanner.scan_as(world, robot, &Pattern::Area(5), &Content::Coin(0))?;
+        /// ```
+        pub fn scan_as<C: Coordinatelike>(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<(C, usize)>> {
+            let (result, _) = self.scan_with_report(world, robot, pattern, content)?;
+            Ok(result.map(|r| (C::from_col_row(r.coordinate.get_col(), r.coordinate.get_row()), r.quantity)))
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan`], additionally returning a
+        /// [`ScanReport`] with statistics about the tiles and energy involved.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the selected `ScanResult` (if any) together with the
+        /// `ScanReport`, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let (result, report) =
+        ///     scanner.scan_with_report(world, robot, &Pattern::Area(5), &Content::Coin(0))?;
+        /// println!("discovered {} tiles for {} energy", report.tiles_discovered, report.energy_consumed);
+        /// ```
+        pub fn scan_with_report(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<(Option<ScanResult>, ScanReport)> {
+            self.with_robot_backend(world, robot, |scanner, backend| scanner.scan_backend(backend, pattern, content))
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan_with_report`] against the given
+        /// [`DiscoveryBackend`] instead of a robot in its world, e.g. an in-memory world in tests.
+        ///
+        /// It is available with the `test_utils` feature.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use resource_scanner_tool::prelude::*;
+        /// use resource_scanner_tool::test_utils::test_world::{FlatWorldGenerator, InMemoryBackend};
+        /// use robotics_lib::world::tile::{Content, TileType};
+        ///
+        /// let mut generator = FlatWorldGenerator::new(10, MapCoordinate::new(4, 4), TileType::Grass)
+        ///     .with_content(6, 4, Content::Coin(2));
+        /// let mut backend = InMemoryBackend::from_generator(&mut generator);
+        /// let (result, report) = ResourceScanner::new()
+        ///     .scan_with_backend(&mut backend, &Pattern::Area(5), &Content::Coin(0))
+        ///     .unwrap();
+        /// assert_eq!(MapCoordinate::new(6, 4), result.unwrap().coordinate);
+        /// assert_eq!(25, report.tiles_discovered);
+        /// ```
+        #[cfg(any(test, feature = "test_utils"))]
+        pub fn scan_with_backend(
+            &mut self,
+            backend: &mut impl DiscoveryBackend,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<(Option<ScanResult>, ScanReport)> {
+            self.scan_backend(backend, pattern, content)
+        }
+
+        /// Performs the scan of [`ResourceScanner::scan_with_report`] through the given backend.
+        fn scan_backend(
+            &mut self,
+            backend: &mut impl DiscoveryBackend,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<(Option<ScanResult>, ScanReport)> {
+            let known = backend.known_map().ok_or(MapUnavailable)?;
+            self.prepare_scan_sized(known.len(), pattern)?;
+
+            let (hashmap, report) = self.discover_known(backend, &known, pattern, self.max_energy, false, None)?;
+            let result = self.select_result(&hashmap, &known, backend.position(), pattern, content);
+            #[cfg(feature = "logging")]
+            log::debug!(
+                "scan for {:?} selected {:?} ({} energy consumed)",
+                content,
+                result.as_ref().map(|r| (r.coordinate, r.quantity)),
+                report.energy_consumed
+            );
+            // return the result
+            Ok((result, report))
+        }
+
+        /// Selects the tile with the highest quantity among the tiles discovered by a scan from
+        /// `position`, remembering it as the result of the last scan.
+        fn select_result(
+            &mut self,
+            hashmap: &HashMap<(usize, usize), Option<Tile>>,
+            known: &[Vec<Option<Tile>>],
+            position: MapCoordinate,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> Option<ScanResult> {
+            let robot_elevation = known
+                .get(position.get_row())
+                .and_then(|row| row.get(position.get_col()))
+                .and_then(|tile| tile.as_ref())
+                .map(|tile| tile.elevation);
+            let result = Self::best_result(hashmap, content, position, robot_elevation);
+            self.last_pattern = Some(pattern.clone());
+            self.last_result = result.as_ref().map(|r| (r.coordinate, r.quantity));
+            result
+        }
+
+        /// Performs the scan described by a [`ScanRequest`], applying its filters before
+        /// selecting the tile with the highest quantity.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `request`: The pattern, content and filters of the scan.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the selected `ScanResult`, if any, or an `Err` under the
+        /// same conditions as `scan`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::Other` if the request filters by elevation and the robot's own tile
+        /// is not known, since the elevation of the robot cannot be determined.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner, ScanRequest};
+        /// let mut scanner = ResourceScanner::new();
+        /// let request = ScanRequest::new(Pattern::Area(5), Content::Coin(0)).with_elevation_range(0..=2);
+        /// if let Some(result) = scanner.scan_request(world, robot, &request)? {
+        ///     println!("{} coins, {:?} levels away", result.quantity, result.elevation_delta);
+        /// }
+        /// ```
+        pub fn scan_request(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            request: &ScanRequest,
+        ) -> ScanOutcome<Option<ScanResult>> {
+            if let Some(quantity) = request.first_at_least {
+                return self.request_first_at_least(world, robot, request, quantity);
+            }
+            let results = self.request_results(world, robot, request)?;
+            Ok(results
+                .into_iter()
+                .max_by_key(|x| selection_key(&x.coordinate, x.quantity)))
+        }
+
+        /// Performs the scan described by a [`ScanRequest`] like
+        /// [`ResourceScanner::scan_request`], returning every tile passing its filters in the
+        /// order of the request, see [`ScanRequest::with_order`].
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `request`: The pattern, content, filters and order of the scan.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the matching tiles, or an `Err` under the same conditions
+        /// as `scan_request`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner, ResultOrder, ScanRequest};
+        /// let mut scanner = ResourceScanner::new();
+        /// let request = ScanRequest::new(Pattern::Area(5), Content::Coin(0)).with_order(ResultOrder::DistanceAsc);
+        /// for result in scanner.scan_request_all(world, robot, &request)? {
+        ///     println!("{} coins at {:?}", result.quantity, result.coordinate);
+        /// }
+        /// ```
+        pub fn scan_request_all(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            request: &ScanRequest,
+        ) -> ScanOutcome<Vec<ScanResult>> {
+            let mut results = self.request_results(world, robot, request)?;
+            request.order.sort(&mut results);
+            Ok(results)
+        }
+
+        /// Performs the scan described by a [`ScanRequest`] like
+        /// [`ResourceScanner::scan_request_all`], keeping only the first `n` tiles in the order of
+        /// the request.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `request`: The pattern, content, filters and order of the scan.
+        /// - `n`: The maximum number of tiles returned.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing at most `n` matching tiles, or an `Err` under the same
+        /// conditions as `scan_request`.
+        pub fn scan_best_n(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            request: &ScanRequest,
+            n: usize,
+        ) -> ScanOutcome<Vec<ScanResult>> {
+            let mut results = self.scan_request_all(world, robot, request)?;
+            results.truncate(n);
+            Ok(results)
+        }
+
+        /// Performs the scan described by a [`ScanRequest`] like
+        /// [`ResourceScanner::scan_request_all`], returning the tiles as [`MatchCoordinate`]s in
+        /// the order of the request.
+        ///
+        /// The tiles are offsets from the robot if the request asks for
+        /// [`relative_coordinates`](ScanRequest::relative_coordinates) or the scanner was built
+        /// `with_relative_coordinates(true)`, absolute coordinates otherwise.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the matching tiles with their quantity, or an `Err` under
+        /// the same conditions as `scan_request`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{MatchCoordinate, Pattern, ResourceScanner, ScanRequest};
+        /// let mut scanner = ResourceScanner::new();
+        /// let request = ScanRequest::new(Pattern::Area(5), Content::Coin(0)).relative_coordinates(true);
+        /// for (coordinate, quantity) in scanner.scan_request_matches(world, robot, &request)? {
+        ///     if let MatchCoordinate::Relative(offset) = coordinate {
+        ///         println!("{} coins {} columns and {} rows away", quantity, offset.get_col(), offset.get_row());
+        ///     }
+        /// }
+        /// ```
+        pub fn scan_request_matches(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            request: &ScanRequest,
+        ) -> ScanOutcome<Vec<(MatchCoordinate, usize)>> {
+            let relative = request.relative_coordinates || self.relative_coordinates;
+            Ok(self
+                .scan_request_all(world, robot, request)?
+                .into_iter()
+                .map(|result| {
+                    let coordinate = if relative {
+                        MatchCoordinate::Relative(RelativeCoordinate::between(&result.origin, &result.coordinate))
+                    } else {
+                        MatchCoordinate::Absolute(result.coordinate)
+                    };
+                    (coordinate, result.quantity)
+                })
+                .collect())
+        }
+
+        /// Discovers the footprint of a [`ScanRequest`] and returns the matching tiles passing its
+        /// filters, in no particular order.
+        fn request_results(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            request: &ScanRequest,
+        ) -> ScanOutcome<Vec<ScanResult>> {
+            self.prepare_scan(world, &request.pattern)?;
+
+            let robot_elevation = ResourceScanner::robot_elevation(world, &*robot);
+            if (request.elevation_range.is_some() || request.line_of_sight) && robot_elevation.is_none() {
+                return Err(ToolError::Other("robot tile unknown".to_string()));
+            }
+            let origin = MapCoordinate::new(
+                robot.get_coordinate().get_col(),
+                robot.get_coordinate().get_row(),
+            );
+            let max_energy = request.max_energy.or(self.max_energy);
+            let (hashmap, _) = self.with_robot_backend(world, robot, |scanner, backend| {
+                scanner.discover_backend(backend, &request.pattern, max_energy, false, request.budget.as_ref())
+            })?;
+            let mut results = Self::matching_results(hashmap, &request.content, origin, robot_elevation);
+            Self::filter_results(world, request, robot_elevation, &mut results);
+            Ok(results)
+        }
+
+        /// Discovers the unknown tiles of the footprint of a [`ScanRequest`] one at a time, nearest
+        /// first, and returns the first tile passing its filters with at least `quantity` of the
+        /// content.
+        fn request_first_at_least(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            request: &ScanRequest,
+            quantity: usize,
+        ) -> ScanOutcome<Option<ScanResult>> {
+            self.prepare_scan(world, &request.pattern)?;
+
+            let robot_elevation = ResourceScanner::robot_elevation(world, &*robot);
+            if (request.elevation_range.is_some() || request.line_of_sight) && robot_elevation.is_none() {
+                return Err(ToolError::Other("robot tile unknown".to_string()));
+            }
+            let origin = MapCoordinate::new(
+                robot.get_coordinate().get_col(),
+                robot.get_coordinate().get_row(),
+            );
+            let coordinates = self.nearest_unknown_coordinates(world, &*robot, &request.pattern);
+            // check the cost of the whole footprint against the cap and the budget
+            let estimated = self.energy_per_tile() * coordinates.len();
+            if let Some(cap) = request.max_energy.or(self.max_energy).filter(|&cap| estimated > cap) {
+                return Err(EnergyCapExceeded { estimated, cap });
+            }
+            if let Some(budget) = &request.budget {
+                if !budget.try_reserve(estimated) {
+                    return Err(BudgetExhausted {
+                        estimated,
+                        remaining: budget.remaining(),
+                    });
+                }
+            }
+            let energy_before = robot.get_energy().get_energy_level();
+            let mut found = None;
+            for coordinate in coordinates {
+                let hashmap = match self.discover_recorded(world, robot, &[coordinate]) {
+                    Ok(hashmap) => hashmap,
+                    Err(error) => {
+                        found = Some(Err(error));
+                        break;
+                    }
+                };
+                let mut results = Self::matching_results(hashmap, &request.content, origin, robot_elevation);
+                Self::filter_results(world, request, robot_elevation, &mut results);
+                if let Some(result) = results.into_iter().find(|result| result.quantity >= quantity) {
+                    found = Some(Ok(result));
+                    break;
+                }
+            }
+            // refund the energy which was not spent
+            if let Some(budget) = &request.budget {
+                let consumed = energy_before.saturating_sub(robot.get_energy().get_energy_level());
+                budget.refund(estimated.saturating_sub(consumed));
+            }
+            match found {
+                Some(Ok(result)) => Ok(Some(result)),
+                Some(Err(error)) => Err(error),
+                None => {
+                    self.record_scan();
+                    Ok(None)
+                }
+            }
+        }
+
+        /// Removes the results not passing the filters of a [`ScanRequest`].
+        fn filter_results(
+            world: &World,
+            request: &ScanRequest,
+            robot_elevation: Option<usize>,
+            results: &mut Vec<ScanResult>,
+        ) {
+            // filter the tiles the robot cannot walk on
+            if request.walkable_only {
+                results.retain(|result| result.walkable);
+            }
+            // filter the tiles by elevation
+            if let Some(elevation_range) = &request.elevation_range {
+                results.retain(|result| {
+                    result
+                        .elevation_delta
+                        .map_or(false, |delta| elevation_range.contains(&delta.unsigned_abs()))
+                });
+            }
+            // filter the tiles hidden behind higher terrain
+            if request.line_of_sight {
+                let known = robot_map(world).unwrap_or_default();
+                let elevation = robot_elevation.unwrap();
+                results.retain(|result| {
+                    ResourceScanner::line_of_sight(
+                        &known,
+                        (result.origin, elevation),
+                        (result.coordinate, result.elevation),
+                    )
+                });
+            }
+        }
+
+        /// Repeats the last scan with the same pattern, discovering only the tiles of its footprint
+        /// that are still unknown, e.g. for a robot parked in place scanning at every tick.
+        ///
+        /// Unlike `scan`, the best tile is chosen among all the known tiles of the footprint, so
+        /// the tiles discovered by the previous scans are still considered. The footprint is
+        /// computed around the current position of the robot.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinates and count of the best tile, if any, or an
+        /// `Err` under the same conditions as `scan`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::Other` if the scanner has not performed a scan yet.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let first = scanner.scan(world, robot, &Pattern::Area(7), &Content::Coin(0))?;
+        /// // on the next ticks, only the tiles still unknown are discovered
+        /// let again = scanner.rescan(world, robot, &Content::Coin(0))?;
+        /// ```
+        pub fn rescan(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            content: &Content,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            let pattern = self
+                .last_pattern
+                .clone()
+                .ok_or(ToolError::Other("no previous scan".to_string()))?;
+            let (tiles, _) = self.footprint_tiles(world, robot, &pattern)?;
+            let result = tiles
+                .into_iter()
+                .filter(|(_, tile)| mem::discriminant(&tile.content) == mem::discriminant(content))
+                .map(|(coordinate, tile)| (coordinate, ResourceScanner::content_quantity(&tile.content)))
+                .max_by_key(|x| selection_key(&x.0, x.1));
+            self.last_result = result;
+            Ok(result)
+        }
+
+        /// Checks whether the area around the robot holds the required content, without looking
+        /// for the best tile.
+        ///
+        /// The unknown tiles of the footprint are discovered one at a time, nearest to the robot
+        /// first (by Manhattan distance, then lowest row and lowest column), and the discovery
+        /// stops at the first tile holding the content. As in `scan`, the tiles known before the
+        /// call are not considered. A near match therefore costs only a few tiles, while a miss
+        /// costs as much as a full scan.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing `true` if a discovered tile holds the content, or an `Err`
+        /// under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if scanner.scan_contains(world, robot, &Pattern::Area(7), &Content::Coin(0))? {
+        ///     println!("there are coins nearby");
+        /// }
+        /// ```
+        pub fn scan_contains(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<bool> {
+            self.prepare_scan(world, pattern)?;
+
+            let sanitized_coordinates = self.nearest_unknown_coordinates(world, &*robot, pattern);
+            // a miss discovers the whole footprint, which must fit within the cap
+            self.check_energy_cap(sanitized_coordinates.len())?;
+            // discover the nearest tiles first, stopping at the first match
+            for coordinate in sanitized_coordinates {
+                let hashmap = self.discover_recorded(world, robot, &[coordinate])?;
+                if !Self::matching_tiles(hashmap, content).is_empty() {
+                    return Ok(true);
+                }
+            }
+            self.record_scan();
+            Ok(false)
+        }
+
+        /// Returns the unknown tiles of the footprint of the pattern, sorted nearest to the robot
+        /// first (by Manhattan distance, then lowest row and lowest column).
+        fn nearest_unknown_coordinates(
+            &mut self,
+            world: &World,
+            robot: &impl Runnable,
+            pattern: &Pattern,
+        ) -> Vec<MapCoordinate> {
+            let position = MapCoordinate::new(
+                robot.get_coordinate().get_col(),
+                robot.get_coordinate().get_row(),
+            );
+            // an unavailable map is a world of size 0, which no pattern fits
+            let world_size = robot_map(world).map_or(0, |map| map.len());
+            let mut target_coordinates = Vec::new();
+            self.last_clipped_count =
+                self.fill_target_coordinates(position, world_size, pattern, &mut target_coordinates);
+            let mut sanitized_coordinates =
+                ResourceScanner::get_sanitized_tiles(world, &target_coordinates);
+            sanitized_coordinates.sort_by_key(|coordinate| {
+                (coordinate.manhattan_distance(&position), coordinate.get_row(), coordinate.get_col())
+            });
+            sanitized_coordinates
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan`], refusing to spend more
+        /// than a fraction of the current energy of the robot.
+        ///
+        /// The estimated cost of the scan, the energy per tile of the scanner for each tile of the
+        /// footprint not yet known to the robot, is compared with `fraction * current_energy`
+        /// before any tile is discovered.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        /// - `fraction`: The largest share of the current energy the scan may spend, in the range
+        ///   `0.0..=1.0`.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinates and count of the best tile, if any, or an
+        /// `Err` under the same conditions as `scan`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::EnergyCapExceeded` if the estimated cost exceeds the fraction of the
+        /// current energy, reported as the cap, and `ToolError::Other` if `fraction` is outside
+        /// `0.0..=1.0`. The robot may still have enough energy for the scan, so
+        /// `ToolError::NotEnoughEnergy` is left to [`ResourceScanner::scan`].
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// // keep at least half of the energy for moving
+        /// let result = scanner.scan_max_fraction(world, robot, &Pattern::Area(7), &Content::Coin(0), 0.5)?;
+        /// ```
+        pub fn scan_max_fraction(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+            fraction: f64,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            if !(0.0..=1.0).contains(&fraction) {
+                return Err(ToolError::Other("fraction out of range".to_string()));
+            }
+            let energy_per_tile = if self.uses_robot_view(pattern) { 0 } else { self.energy_per_tile() };
+            let estimated = energy_per_tile * self.plan(world, &*robot, pattern).sanitized.len();
+            let allowed = fraction * robot.get_energy().get_energy_level() as f64;
+            if estimated as f64 > allowed {
+                return Err(EnergyCapExceeded {
+                    estimated,
+                    cap: allowed as usize,
+                });
+            }
+            self.scan(world, robot, pattern, content)
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan`], shrinking the pattern
+        /// until the robot has enough energy for it.
+        ///
+        /// The estimated cost of the scan, as in [`ResourceScanner::with_max_energy`], is compared
+        /// with the energy of the robot before any tile is discovered.
+        /// While it is too high, the size of the pattern is reduced by one, skipping the sizes
+        /// not valid for the pattern (e.g. the even sizes of `Area`), and the scan is performed
+        /// with the largest size that fits.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The largest pattern to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinates and count of the best tile found with
+        /// the largest affordable size, if any, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::NotEnoughEnergy` if even the smallest size is too expensive.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// // scan an Area(9), or the largest area the robot can afford
+        /// let result = scanner.scan_adaptive(world, robot, &Pattern::Area(9), &Content::Coin(0))?;
+        /// ```
+        pub fn scan_adaptive(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            let world_size = robot_map(world).map_or(0, |map| map.len());
+            // let the scan report an invalid pattern
+            if !pattern.check_size(world_size) {
+                return self.scan(world, robot, pattern, content);
+            }
+            let energy = robot.get_energy().get_energy_level();
+            // the cost of the smallest size tried so far
+            let mut required = 0;
+            for size in (1..=pattern.size()).rev() {
+                let candidate = pattern.with_size(size);
+                if !candidate.check_size(world_size) {
+                    continue;
+                }
+                let energy_per_tile = if self.uses_robot_view(&candidate) { 0 } else { self.energy_per_tile() };
+                let estimated = energy_per_tile * self.plan(world, &*robot, &candidate).sanitized.len();
+                if estimated <= energy {
+                    return self.scan(world, robot, &candidate, content);
+                }
+                required = estimated;
+            }
+            Err(NotEnoughEnergy {
+                required,
+                available: energy,
+            })
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan`], discovering only part
+        /// of the footprint when the whole of it would be too expensive.
+        ///
+        /// The energy available to the scan is the energy of the robot, further limited by the
+        /// cap of [`ResourceScanner::with_max_energy`], if any. When the unknown tiles of the
+        /// footprint cost more than that, only the ones nearest to the robot (by Manhattan
+        /// distance, then lowest row and lowest column) are discovered, each for the energy per
+        /// tile of the scanner.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the same selection as `scan` over the discovered tiles,
+        /// or an `Err` if the pattern is invalid or the scanner is cooling down.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// // spend at most 60 energy, whatever the size of the pattern
+        /// let mut scanner = ResourceScanner::new().with_max_energy(60);
+        /// let result = scanner.scan_partial(world, robot, &Pattern::Area(9), &Content::Coin(0))?;
+        /// ```
+        pub fn scan_partial(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            self.prepare_scan(world, pattern)?;
+
+            let energy = robot.get_energy().get_energy_level();
+            let cap = self.max_energy.map_or(energy, |max_energy| max_energy.min(energy));
+            let (hashmap, _) = self.with_robot_backend(world, robot, |scanner, backend| {
+                scanner.discover_backend(backend, pattern, Some(cap), true, None)
+            })?;
+            Ok(Self::matching_tiles(hashmap, content)
+                .into_iter()
+                .max_by_key(|x| selection_key(&x.0, x.1)))
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan`], returning every tile
+        /// containing the required content instead of only the richest one.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the matching tiles as [`ScanResults`], in no particular
+        /// order, or an `Err` under the same conditions as `scan`. Searching for `Content::None`
+        /// lists the empty tiles, each with quantity 0.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let results = scanner.scan_all(world, robot, &Pattern::Area(5), &Content::Coin(0))?;
+        /// for (coordinate, quantity) in &results {
+        ///     println!("{} coins at {:?}", quantity, coordinate);
+        /// }
+        /// println!("best tile: {:?}, total: {}", results.best(), results.total());
+        /// ```
+        pub fn scan_all(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<ScanResults> {
+            let mut results = Vec::new();
+            self.scan_into(world, robot, pattern, content, &mut results)?;
+            Ok(ScanResults(results))
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan_all`], returning the
+        /// matching tiles sorted by coordinate, by row and then by column, so that the output of
+        /// a scan of the same world is always the same, e.g. for snapshot tests.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the matching tiles with their quantity, or an `Err` under
+        /// the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let results = scanner.scan_all_sorted(world, robot, &Pattern::Area(5), &Content::Coin(0))?;
+        /// assert_snapshot!(format!("{:?}", results));
+        /// ```
+        pub fn scan_all_sorted(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Vec<(MapCoordinate, usize)>> {
+            let mut results = Vec::new();
+            self.scan_into(world, robot, pattern, content, &mut results)?;
+            results.sort_by_key(|(coordinate, _)| (coordinate.row(), coordinate.col()));
+            Ok(results)
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan_all`], keeping only the `n`
+        /// tiles with the highest quantity, e.g. to limit the work done on the matches of a large
+        /// pattern.
+        ///
+        /// Only the `n` best tiles are sorted, after partitioning them from the rest of the
+        /// matches.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        /// - `n`: The maximum number of tiles returned.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing at most `n` matching tiles, by descending quantity with
+        /// the same tie-breaking as `scan`, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// for (coordinate, quantity) in scanner.scan_top(world, robot, &Pattern::Area(9), &Content::Tree(0), 3)? {
+        ///     println!("{} trees at {:?}", quantity, coordinate);
+        /// }
+        /// ```
+        pub fn scan_top(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+            n: usize,
+        ) -> ScanOutcome<Vec<(MapCoordinate, usize)>> {
+            let mut results = Vec::new();
+            self.scan_into(world, robot, pattern, content, &mut results)?;
+            let key = |(coordinate, quantity): &(MapCoordinate, usize)| Reverse(selection_key(coordinate, *quantity));
+            // move the n best tiles in front, then sort only them
+            if n < results.len() {
+                results.select_nth_unstable_by_key(n, key);
+                results.truncate(n);
+            }
+            results.sort_unstable_by_key(key);
+            Ok(results)
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan_all`], summarizing the
+        /// matching tiles with the richest one, their total quantity and their number, from a
+        /// single discovery.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the [`ScanSummary`] of the matching tiles, `None` if no
+        /// tile matches, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if let Some(summary) = scanner.scan_summary(world, robot, &Pattern::Area(7), &Content::Rock(0))? {
+        ///     println!("{} rocks in {} piles, the largest at {:?}", summary.total, summary.count, summary.best.0);
+        /// }
+        /// ```
+        pub fn scan_summary(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<ScanSummary>> {
+            let mut results = Vec::new();
+            self.scan_into(world, robot, pattern, content, &mut results)?;
+            let Some(best) = results.iter().copied().max_by_key(|x| selection_key(&x.0, x.1)) else {
+                return Ok(None);
+            };
+            Ok(Some(ScanSummary {
+                best,
+                total: results.iter().map(|(_, quantity)| quantity).sum(),
+                count: results.len(),
+            }))
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan`], with the pattern
+        /// expressed relative to the direction the robot is facing: the up direction of the
+        /// pattern is turned to `heading`, see [`Pattern::oriented`].
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `heading`: The direction the robot is facing.
+        /// - `pattern`: The pattern defining the area to be scanned, as seen facing up.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinates and count of the selected tile, if any, or
+        /// an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// use robotics_lib::interface::Direction;
+        /// let mut scanner = ResourceScanner::new();
+        /// // look 3 tiles ahead of a robot facing left
+        /// let ahead = scanner.scan_facing(world, robot, Direction::Left, &Pattern::DirectionUp(3), &Content::Coin(0))?;
+        /// ```
+        pub fn scan_facing(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            heading: Direction,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            self.scan(world, robot, &pattern.oriented(heading), content)
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan`], selecting the best tile
+        /// among both the tiles discovered by the scan and the tiles of the pattern the robot
+        /// already knew.
+        ///
+        /// `scan` skips the known tiles, so a richer tile discovered earlier is not considered;
+        /// this scan picks it, so that the result reflects everything the robot knows about the
+        /// area. The known tiles cost no energy.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinates and count of the selected tile, if any,
+        /// with the same tie-breaking as `scan`, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if let Some((coordinate, quantity)) = scanner.scan_including_known(world, robot, &Pattern::Area(5), &Content::Coin(0))? {
+        ///     println!("{} coins at {:?}", quantity, coordinate);
+        /// }
+        /// ```
+        pub fn scan_including_known(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            self.prepare_scan(world, pattern)?;
+
+            let (mut hashmap, _) = self.discover(world, robot, pattern)?;
+            // add the tiles of the pattern known to the robot, which the discovery skipped
+            let known = robot_map(world).ok_or(MapUnavailable)?;
+            let origin = MapCoordinate::new(robot.get_coordinate().get_col(), robot.get_coordinate().get_row());
+            for coordinate in self.pattern_coordinates(origin, known.len(), pattern) {
+                if let Some(tile) = &known[coordinate.row()][coordinate.col()] {
+                    hashmap.insert(coordinate.as_col_row(), Some(tile.clone()));
+                }
+            }
+            Ok(Self::best_result(&hashmap, content, origin, None).map(|r| (r.coordinate, r.quantity)))
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan_all`], sending the matching tiles
+        /// through a channel instead of collecting them, e.g. to feed the next stage of a
+        /// pipeline.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        /// - `tx`: The sender receiving the `(coordinate, quantity)` pairs, in no particular order.
+        ///
+        /// # Errors
+        ///
+        /// Returns an `Err` under the same conditions as `scan`, and `ToolError::Other` if the
+        /// receiver was dropped before all the tiles were sent.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// use std::sync::mpsc::channel;
+        /// let (tx, rx) = channel();
+        /// let mut scanner = ResourceScanner::new();
+        /// scanner.scan_stream(world, robot, &Pattern::Area(5), &Content::Coin(0), tx)?;
+        /// for (coordinate, quantity) in rx {
+        ///     println!("{} coins at {:?}", quantity, coordinate);
+        /// }
+        /// ```
+        pub fn scan_stream(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+            tx: Sender<(MapCoordinate, usize)>,
+        ) -> ScanOutcome<()> {
+            let mut results = Vec::new();
+            self.scan_into(world, robot, pattern, content, &mut results)?;
+            for result in results {
+                tx.send(result)
+                    .map_err(|_| ToolError::Other("receiver disconnected".to_string()))?;
+            }
+            Ok(())
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan_all`], keeping only the tiles whose
+        /// quantity of content falls within `quantity`, e.g. `3..=7` for the coin tiles holding 3
+        /// to 7 coins.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        /// - `quantity`: The range of quantities to keep, bounds included.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the `(coordinate, quantity)` pairs of the matching tiles
+        /// within the range, in no particular order, or an `Err` under the same conditions as
+        /// `scan`.
+        pub fn scan_range(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+            quantity: RangeInclusive<usize>,
+        ) -> ScanOutcome<Vec<(MapCoordinate, usize)>> {
+            let mut results = Vec::new();
+            self.scan_into(world, robot, pattern, content, &mut results)?;
+            results.retain(|(_, found)| quantity.contains(found));
+            Ok(results)
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan_all`], reporting the coordinates of
+        /// the matching tiles in the frame chosen with
+        /// [`ResourceScanner::with_relative_coordinates`].
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the `(coordinate, quantity)` pairs of the matching tiles,
+        /// sorted by row and then by column, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{MatchCoordinate, Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new().with_relative_coordinates(true);
+        /// for (coordinate, quantity) in scanner.scan_matches(world, robot, &Pattern::Area(5), &Content::Coin(0))? {
+        ///     if let MatchCoordinate::Relative(offset) = coordinate {
+        ///         println!("{} coins {} columns and {} rows away", quantity, offset.get_col(), offset.get_row());
+        ///     }
+        /// }
+        /// ```
+        pub fn scan_matches(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Vec<(MatchCoordinate, usize)>> {
+            let origin = MapCoordinate::new(robot.get_coordinate().get_col(), robot.get_coordinate().get_row());
+            let mut results = Vec::new();
+            self.scan_into(world, robot, pattern, content, &mut results)?;
+            results.sort_by_key(|(coordinate, _)| (coordinate.get_row(), coordinate.get_col()));
+            Ok(results
+                .into_iter()
+                .map(|(coordinate, quantity)| {
+                    let coordinate = if self.relative_coordinates {
+                        MatchCoordinate::Relative(RelativeCoordinate::between(&origin, &coordinate))
+                    } else {
+                        MatchCoordinate::Absolute(coordinate)
+                    };
+                    (coordinate, quantity)
+                })
+                .collect())
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan_all`], writing the matching tiles
+        /// into a caller-provided buffer instead of allocating a new one.
+        ///
+        /// The buffer is cleared before scanning, also when the scan fails, and keeps its
+        /// capacity, so it can be reused across scans to avoid per-tick allocations.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        /// - `out`: The buffer receiving the `(coordinate, quantity)` pairs, in no particular order.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let mut buffer = Vec::with_capacity(25);
+        /// scanner.scan_into(world, robot, &Pattern::Area(5), &Content::Coin(0), &mut buffer)?;
+        /// ```
+        pub fn scan_into(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+            out: &mut Vec<(MapCoordinate, usize)>,
+        ) -> ScanOutcome<()> {
+            out.clear();
+            self.prepare_scan(world, pattern)?;
+
+            let (hashmap, _) = self.discover(world, robot, pattern)?;
+            // push only the tiles containing the requested content
+            for (key, val) in hashmap.iter() {
+                if let Some(tile) = val {
+                    if mem::discriminant(&tile.content) == mem::discriminant(content) {
+                        out.push((MapCoordinate::from(*key), ResourceScanner::content_quantity(&tile.content)));
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan_all`] and returns the
+        /// matching coordinates in a visiting order.
+        ///
+        /// The order is built greedily: starting from the robot, the next coordinate is always the
+        /// closest one to the previous according to the Manhattan distance, ties broken by lowest
+        /// row first, then lowest column. It is a convenience for planning, not an optimal tour.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the ordered coordinates, empty if nothing was found, or an
+        /// `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// for waypoint in scanner.scan_route(world, robot, &Pattern::Area(5), &Content::Coin(0))? {
+        ///     println!("next coin at {:?}", waypoint);
+        /// }
+        /// ```
+        pub fn scan_route(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Vec<MapCoordinate>> {
+            let mut remaining: Vec<MapCoordinate> = self
+                .scan_all(world, robot, pattern, content)?
+                .into_iter()
+                .map(|(coordinate, _)| coordinate)
+                .collect();
+            let mut route = Vec::with_capacity(remaining.len());
+            let (mut x_current, mut y_current) = (
+                robot.get_coordinate().get_col(),
+                robot.get_coordinate().get_row(),
+            );
+            // visit the closest remaining coordinate until none is left
+            while let Some(index) = (0..remaining.len()).min_by_key(|&index| {
+                let coordinate = &remaining[index];
+                (
+                    coordinate.get_col().abs_diff(x_current) + coordinate.get_row().abs_diff(y_current),
+                    coordinate.get_row(),
+                    coordinate.get_col(),
+                )
+            }) {
+                let next = remaining.swap_remove(index);
+                x_current = next.get_col();
+                y_current = next.get_row();
+                route.push(next);
+            }
+            Ok(route)
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan`], yielding to the async runtime
+        /// between batches of [`SCAN_BATCH_SIZE`] discovered tiles so that other tasks can progress
+        /// during very large scans.
+        ///
+        /// The scan is validated and recorded like a sync one, e.g. in
+        /// [`ResourceScanner::last_result`] and [`ResourceScanner::total_energy_consumed`].
+        ///
+        /// It is available with the `tokio` feature.
+        ///
+        /// # Energy Cost
+        ///
+        /// The energy semantics are identical to the sync version: the same tiles are discovered
+        /// for the same cost, and the energy needed for the whole scan is checked before the first
+        /// batch, so a scan never stops halfway for lack of energy.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let result = scanner.scan_async(world, robot, &Pattern::Area(31), &Content::Coin(0)).await?;
+        /// ```
+        #[cfg(feature = "tokio")]
+        pub async fn scan_async(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            self.scan_async_until(world, robot, pattern, content, None).await
+        }
+
+        /// Performs the same scan as [`ResourceScanner::scan_async`], stopping before the next
+        /// batch once `cancel` is set, e.g. by another task giving up on the scan.
+        ///
+        /// The tiles discovered before the cancellation are recorded like the ones of a completed
+        /// scan, and their energy is not given back. The `robot_view` shortcut of `Area(3)` is
+        /// served at once and cannot be cancelled.
+        ///
+        /// It is available with the `tokio` feature.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::Cancelled` if `cancel` was set before the last batch, and an `Err`
+        /// under the same conditions as `scan_async` otherwise.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// use std::sync::atomic::{AtomicBool, Ordering};
+        /// let cancel = AtomicBool::new(false);
+        /// let mut scanner = ResourceScanner::new();
+        /// // another task calls cancel.store(true, Ordering::Relaxed) to stop the scan
+        /// let result = scanner.scan_async_cancellable(world, robot, &Pattern::Area(31), &Content::Coin(0), &cancel).await;
+        /// ```
+        #[cfg(feature = "tokio")]
+        pub async fn scan_async_cancellable(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+            cancel: &AtomicBool,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            self.scan_async_until(world, robot, pattern, content, Some(cancel)).await
+        }
+
+        /// Runs the scans of [`ResourceScanner::scan_async`], checking `cancel`, if any, before
+        /// each batch.
+        #[cfg(feature = "tokio")]
+        async fn scan_async_until(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+            cancel: Option<&AtomicBool>,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            let known = robot_map(world).ok_or(MapUnavailable)?;
+            self.prepare_scan_sized(known.len(), pattern)?;
+            // the backend is held across the yields, so the buffer of the keys is lent by hand
+            let mut backend = RobotBackend::new(world, robot, mem::take(&mut self.row_col_keys));
+            let planned = self.plan_discovery(&backend, &known, pattern, self.max_energy, false, None);
+            let position = backend.position();
+            let available = backend.energy_level();
+            let (coordinates, report) = match planned {
+                Ok(planned) => planned,
+                Err(error) => {
+                    self.row_col_keys = backend.into_keys();
+                    return Err(error);
+                }
+            };
+            // the energy of the whole scan is checked before the first batch
+            if available < report.energy_estimated {
+                self.coordinate_buffer = coordinates;
+                self.row_col_keys = backend.into_keys();
+                return Err(NotEnoughEnergy {
+                    required: report.energy_estimated,
+                    available,
+                });
+            }
+
+            // discover the tiles batch by batch into a single map, the robot_view shortcut at once
+            let mut tiles = HashMap::new();
+            let mut cancelled = false;
+            let outcome = if report.used_robot_view {
+                backend.view(&coordinates, &mut tiles)
+            } else {
+                let mut outcome = Ok(());
+                for batch in coordinates.chunks(SCAN_BATCH_SIZE) {
+                    if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+                        cancelled = true;
+                        break;
+                    }
+                    outcome = backend.discover(batch, &mut tiles);
+                    if outcome.is_err() {
+                        break;
+                    }
+                    tokio::task::yield_now().await;
+                }
+                outcome
+            };
+            let energy_after = backend.energy_level();
+            self.row_col_keys = backend.into_keys();
+            let requested = coordinates.len();
+            self.coordinate_buffer = coordinates;
+            let (hashmap, _) =
+                self.finish_discovery(requested, outcome.map(|()| tiles), available, energy_after, None, report)?;
+            if cancelled {
+                return Err(Cancelled);
+            }
+            let result = self.select_result(&hashmap, &known, position, pattern, content);
+            Ok(result.map(|r| (r.coordinate, r.quantity)))
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan`], ignoring the matching
+        /// tiles the robot cannot walk on.
+        ///
+        /// Traversability is game-specific, so it is decided by the `walkable` predicate, which is
+        /// called on every matching tile before the best one is selected.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        /// - `walkable`: Returns `true` if the robot can walk on the given tile.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the walkable tile with the highest quantity, if any, or an
+        /// `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let result = scanner.scan_reachable(world, robot, &Pattern::Area(5), &Content::Fish(0), |tile| {
+        ///     !matches!(tile.tile_type, TileType::DeepWater | TileType::Lava)
+        /// })?;
+        /// ```
+        pub fn scan_reachable(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+            walkable: fn(&Tile) -> bool,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            self.prepare_scan(world, pattern)?;
+
+            let (mut hashmap, _) = self.discover(world, robot, pattern)?;
+            // drop the tiles the robot cannot reach
+            hashmap.retain(|_key, val| val.as_ref().map_or(false, walkable));
+            Ok(Self::matching_tiles(hashmap, content)
+                .into_iter()
+                .max_by_key(|x| selection_key(&x.0, x.1)))
+        }
+
+        /// Scans an area around the robot for several contents at once, returning the best tile of
+        /// the first content in `priorities` found in the area.
+        ///
+        /// The tiles are discovered in a single pass, the energy cost is therefore the same as
+        /// for a single [`ResourceScanner::scan`].
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `priorities`: The contents to be searched for, from the most to the least preferred.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing either:
+        /// - `Some((index, coordinates, count))`: where `index` is the position in `priorities` of
+        ///   the content found, and `coordinates` and `count` describe its best tile as in `scan`.
+        /// - `None`: If none of the contents is found, or if `priorities` is empty, in which case
+        ///   no tile is discovered.
+        /// - `Err`: under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let priorities = [Content::Coin(0), Content::Garbage(0), Content::Tree(0)];
+        /// if let Some((index, coordinate, _)) = scanner.scan_priority(world, robot, &Pattern::Area(5), &priorities)? {
+        ///     println!("heading to {:?} for {:?}", coordinate, priorities[index]);
+        /// }
+        /// ```
+        pub fn scan_priority(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            priorities: &[Content],
+        ) -> ScanOutcome<Option<(usize, MapCoordinate, usize)>> {
+            if priorities.is_empty() {
+                return Ok(None);
+            }
+            self.prepare_scan(world, pattern)?;
+
+            let (hashmap, _) = self.discover(world, robot, pattern)?;
+            // walk the priorities and stop at the first content found
+            for (index, content) in priorities.iter().enumerate() {
+                let best = Self::matching_tiles(hashmap.clone(), content)
+                    .into_iter()
+                    .max_by_key(|x| selection_key(&x.0, x.1));
+                if let Some((coordinate, quantity)) = best {
+                    return Ok(Some((index, coordinate, quantity)));
+                }
+            }
+            Ok(None)
+        }
+
+        /// Scans an area around the robot for several contents at once, returning the best tile of
+        /// each content found in the area.
+        ///
+        /// This is the one-pass version of calling [`ResourceScanner::scan`] once per content: the
+        /// tiles are discovered a single time, so the energy cost is the same as for one scan.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `contents`: The contents to be searched for.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing a map from each requested content to the coordinates and
+        /// count of its best tile, chosen as in `scan`. The contents which are not found are absent
+        /// from the map. An `Err` is returned under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let contents = [Content::Coin(0), Content::Tree(0)];
+        /// let best = scanner.scan_best_per_content(world, robot, &Pattern::Area(5), &contents)?;
+        /// if let Some((coordinate, count)) = best.get(&Content::Tree(0)) {
+        ///     println!("{} trees at {:?}", count, coordinate);
+        /// }
+        /// ```
+        pub fn scan_best_per_content(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            contents: &[Content],
+        ) -> ScanOutcome<HashMap<Content, (MapCoordinate, usize)>> {
+            self.prepare_scan(world, pattern)?;
+
+            let (hashmap, _) = self.discover(world, robot, pattern)?;
+            let mut out = HashMap::new();
+            for content in contents {
+                let best = Self::matching_tiles(hashmap.clone(), content)
+                    .into_iter()
+                    .max_by_key(|x| selection_key(&x.0, x.1));
+                if let Some(best) = best {
+                    out.insert(content.clone(), best);
+                }
+            }
+            Ok(out)
+        }
+
+        /// Scans an area around the robot for any content of a category, e.g. any hazard,
+        /// without listing the contents one by one.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `category`: The category of the contents to be searched for, see [`ContentCategory`].
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinates, the content and the quantity of the
+        /// matching tile with the highest quantity, whatever its content, chosen as in `scan`, or
+        /// `None` if no content of the category is found. An `Err` is returned under the same
+        /// conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{ContentCategory, Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if let Some((coordinate, content, _)) = scanner.scan_category(world, robot, &Pattern::Area(5), ContentCategory::Hazard)? {
+        ///     println!("{:?} at {:?}", content, coordinate);
+        /// }
+        /// ```
+        pub fn scan_category(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            category: ContentCategory,
+        ) -> ScanOutcome<Option<(MapCoordinate, Content, usize)>> {
+            self.prepare_scan(world, pattern)?;
+
+            let (hashmap, _) = self.discover(world, robot, pattern)?;
+            Ok(hashmap
+                .into_iter()
+                .filter_map(|(key, tile)| {
+                    let tile = tile.filter(|tile| category.contains(&tile.content))?;
+                    let quantity = ResourceScanner::content_quantity(&tile.content);
+                    Some((MapCoordinate::from(key), tile.content, quantity))
+                })
+                .max_by_key(|x| selection_key(&x.0, x.2)))
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan`], selecting the matching
+        /// tile by elevation instead of by quantity.
+        ///
+        /// Among the tiles at the preferred elevation, the one with the highest quantity is
+        /// returned, with the same tie-breaking as `scan`.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        /// - `prefer`: Whether the lowest or the highest tile is selected.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinates, count and elevation of the selected
+        /// tile, if any, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{ElevationPreference, Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let lowest = scanner.scan_by_elevation(
+        ///     world,
+        ///     robot,
+        ///     &Pattern::Area(5),
+        ///     &Content::Water(0),
+        ///     ElevationPreference::Lowest,
+        /// )?;
+        /// ```
+        pub fn scan_by_elevation(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+            prefer: ElevationPreference,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize, usize)>> {
+            self.prepare_scan(world, pattern)?;
+
+            let origin = MapCoordinate::new(
+                robot.get_coordinate().get_col(),
+                robot.get_coordinate().get_row(),
+            );
+            let (hashmap, _) = self.discover(world, robot, pattern)?;
+            let results = Self::matching_results(hashmap, content, origin, None);
+            // rank the elevation first, negated when the lowest tile is preferred
+            let result = results.into_iter().max_by_key(|x| {
+                let elevation = match prefer {
+                    ElevationPreference::Lowest => -(x.elevation as isize),
+                    ElevationPreference::Highest => x.elevation as isize,
+                };
+                (elevation, selection_key(&x.coordinate, x.quantity))
+            });
+            Ok(result.map(|x| (x.coordinate, x.quantity, x.elevation)))
+        }
+
+        /// Discovers the footprint of a pattern and returns every tile it discovered, without any
+        /// filter, for the users running their own analysis.
+        ///
+        /// Tiles known before the scan are skipped and the energy is checked as in
+        /// [`ResourceScanner::scan`]. Coordinates left unknown by the discovery are omitted.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the discovered tiles keyed by coordinate, or an `Err`
+        /// under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let tiles = scanner.scan_raw(world, robot, &Pattern::Area(5))?;
+        /// let water = tiles.values().filter(|tile| tile.tile_type == TileType::ShallowWater).count();
+        /// ```
+        pub fn scan_raw(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+        ) -> ScanOutcome<HashMap<MapCoordinate, Tile>> {
+            self.prepare_scan(world, pattern)?;
+            let (hashmap, _) = self.discover(world, robot, pattern)?;
+            Ok(hashmap
+                .into_iter()
+                .filter_map(|(key, tile)| Some((MapCoordinate::from(key), tile?)))
+                .collect())
+        }
+
+        /// Scans an area around the robot for the tiles holding the given content that the scanner
+        /// has not reported yet, so that each deposit is processed exactly once.
+        ///
+        /// Unknown tiles are discovered as in [`ResourceScanner::scan`], while tiles already known
+        /// are read from the robot map, so a deposit seen by another tool is reported as well. The
+        /// scanner remembers the coordinates it reports and excludes them from the following
+        /// calls until [`ResourceScanner::reset_reported`] is called.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the matching tiles not reported before as
+        /// [`ScanResults`], in the generation order of the pattern, or an `Err` under the same
+        /// conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// for (coordinate, quantity) in &scanner.scan_unreported(world, robot, &Pattern::Area(5), &Content::Rock(0))? {
+        ///     println!("new deposit of {} rocks at {:?}", quantity, coordinate);
+        /// }
+        /// ```
+        pub fn scan_unreported(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<ScanResults> {
+            let (tiles, _) = self.footprint_tiles(world, robot, pattern)?;
+            let mut results = Vec::new();
+            for (coordinate, tile) in tiles {
+                if mem::discriminant(&tile.content) == mem::discriminant(content)
+                    && self.reported.insert(coordinate)
+                {
+                    results.push((coordinate, ResourceScanner::content_quantity(&tile.content)));
+                }
+            }
+            Ok(ScanResults(results))
+        }
+
+        /// Forgets the tiles reported by [`ResourceScanner::scan_unreported`], so that they are
+        /// reported again by the next calls.
+        pub fn reset_reported(&mut self) {
+            self.reported.clear();
+        }
+
+        /// Scans an area around the robot for the tiles that do NOT contain the given content,
+        /// e.g. to find the tiles free from `Content::Fire`.
+        ///
+        /// Unknown tiles are discovered as in [`ResourceScanner::scan`], while tiles already known
+        /// are read from the robot map, so the answer covers the whole footprint of the pattern.
+        /// Tiles still unknown after the discovery are never reported.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content that must be absent.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinates of the tiles without the content, in the
+        /// generation order of the pattern, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let safe = scanner.scan_absent(world, robot, &Pattern::Area(5), &Content::Fire)?;
+        /// ```
+        pub fn scan_absent(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Vec<MapCoordinate>> {
+            let tiles = self.absent_tiles(world, robot, pattern, content)?;
+            Ok(tiles.into_iter().map(|(coordinate, _)| coordinate).collect())
+        }
+
+        /// Returns the walkable tile without the given content closest to the robot, according
+        /// to the Manhattan distance.
+        ///
+        /// It is built on top of [`ResourceScanner::scan_absent`], and walkability is decided by
+        /// the properties of the `TileType` of the tiles. Ties are broken by coordinate order:
+        /// lowest row first, then lowest column. The tile of the robot itself is a candidate when
+        /// the scanner includes the center.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content that must be absent.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing the coordinate of the tile, `None` if every tile of the
+        /// footprint holds the content or cannot be walked on, or an `Err` under the same
+        /// conditions as `scan`.
+        pub fn nearest_safe_tile(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<MapCoordinate>> {
+            let tiles = self.absent_tiles(world, robot, pattern, content)?;
+            let x_robot = robot.get_coordinate().get_col();
+            let y_robot = robot.get_coordinate().get_row();
+            Ok(tiles
+                .into_iter()
+                .filter(|(_, tile)| tile.tile_type.properties().walk())
+                .map(|(coordinate, _)| coordinate)
+                .min_by_key(|coordinate| {
+                    (
+                        coordinate.get_col().abs_diff(x_robot) + coordinate.get_row().abs_diff(y_robot),
+                        coordinate.get_row(),
+                        coordinate.get_col(),
+                    )
+                }))
+        }
+
+        /// Discovers the footprint of the pattern and returns the known tiles of the footprint which
+        /// do not hold the given content, in the generation order of the pattern.
+        fn absent_tiles(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Vec<(MapCoordinate, Tile)>> {
+            let (mut tiles, _) = self.footprint_tiles(world, robot, pattern)?;
+            tiles.retain(|(_, tile)| mem::discriminant(&tile.content) != mem::discriminant(content));
+            Ok(tiles)
+        }
+
+        /// Discovers the footprint of the pattern and returns all its known tiles, whether they
+        /// were just discovered or already known, in the generation order of the pattern.
+        ///
+        /// Tiles still unknown after the discovery are omitted.
+        fn footprint_tiles(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+        ) -> ScanOutcome<(Vec<(MapCoordinate, Tile)>, ScanReport)> {
+            self.prepare_scan(world, pattern)?;
+
+            let footprint =
+                self.get_target_coordinates(&*robot, world, pattern)
+                    .unwrap_or_default();
+            let (_, report) = self.discover(world, robot, pattern)?;
+            // read the whole footprint from the robot map, now including the discovered tiles
+            let known = robot_map(world).unwrap_or_default();
+            let mut tiles = Vec::new();
+            for coordinate in footprint {
+                let tile = known
+                    .get(coordinate.get_row())
+                    .and_then(|row| row.get(coordinate.get_col()))
+                    .and_then(|tile| tile.as_ref());
+                if let Some(tile) = tile {
+                    self.record_sighting(coordinate, &tile.content);
+                    tiles.push((coordinate, tile.clone()));
+                }
+            }
+            Ok((tiles, report))
+        }
+
+        /// Answers several queries about the footprint of a pattern with a single discovery pass.
+        ///
+        /// Unlike [`ResourceScanner::scan`], the queries are evaluated against every known tile of
+        /// the footprint, including the tiles known before the scan. The energy cost is the same as
+        /// for a single `scan` with the same pattern.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `queries`: The questions to answer, see [`Query`].
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing one [`QueryResult`] per query, in the same order, together
+        /// with the `ScanReport` of the discovery, or an `Err` under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, Query, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// let queries = vec![Query::ContentMax(Content::Coin(0)), Query::Hazards];
+        /// let (answers, report) = scanner.scan_batch(world, robot, &Pattern::Area(5), queries)?;
+        /// ```
+        pub fn scan_batch(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            queries: Vec<Query>,
+        ) -> ScanOutcome<(Vec<QueryResult>, ScanReport)> {
+            let (tiles, report) = self.footprint_tiles(world, robot, pattern)?;
+            let x_robot = robot.get_coordinate().get_col();
+            let y_robot = robot.get_coordinate().get_row();
+            // collect the tiles holding the given content with their quantity
+            let matching = |content: &Content| {
+                tiles
+                    .iter()
+                    .filter(|(_, tile)| mem::discriminant(&tile.content) == mem::discriminant(content))
+                    .map(|(coordinate, tile)| (*coordinate, ResourceScanner::content_quantity(&tile.content)))
+                    .collect::<Vec<_>>()
+            };
+
+            let mut answers = Vec::with_capacity(queries.len());
+            for query in queries {
+                let answer = match query {
+                    Query::ContentMax(content) => QueryResult::ContentMax(
+                        matching(&content)
+                            .into_iter()
+                            .max_by_key(|x| selection_key(&x.0, x.1)),
+                    ),
+                    Query::ContentNearest(content) => QueryResult::ContentNearest(
+                        matching(&content).into_iter().min_by_key(|(coordinate, _)| {
+                            (
+                                coordinate.get_col().abs_diff(x_robot) + coordinate.get_row().abs_diff(y_robot),
+                                coordinate.get_row(),
+                                coordinate.get_col(),
+                            )
+                        }),
+                    ),
+                    Query::TileType(tile_type) => QueryResult::TileType(
+                        tiles
+                            .iter()
+                            .filter(|(_, tile)| tile.tile_type == tile_type)
+                            .map(|(coordinate, _)| *coordinate)
+                            .collect(),
+                    ),
+                    Query::Hazards => QueryResult::Hazards(
+                        tiles
+                            .iter()
+                            .filter(|(_, tile)| {
+                                matches!(tile.content, Content::Fire)
+                                    || matches!(tile.tile_type, TileType::Lava | TileType::DeepWater)
+                            })
+                            .map(|(coordinate, _)| *coordinate)
+                            .collect(),
+                    ),
+                };
+                answers.push(answer);
+            }
+            Ok((answers, report))
+        }
+
+        /// Scans an area around the robot like [`ResourceScanner::scan_all`], returning the
+        /// quantity-weighted centroid of all the matching tiles instead of a single tile.
+        ///
+        /// ## Rounding
+        ///
+        /// Each axis of the centroid is rounded to the nearest integer, with halves rounded up
+        /// (towards the right or the bottom of the map). The computation uses integer arithmetic
+        /// only, and the result is clamped to the bounding box of the matching tiles, so it is
+        /// always inside the world even when the matches hug its edge.
+        ///
+        /// For example, two piles of 3 coins in `(2, 5)` and `(8, 5)` and a single coin in `(5, 5)`
+        /// have their centroid exactly in `(5, 5)`, while a single coin in `(2, 5)` and 3 coins in
+        /// `(5, 5)` give `(4.25, 5)`, rounded to `(4, 5)`.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `pattern`: The pattern defining the area to be scanned.
+        /// - `content`: The content to be searched for in the area.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing:
+        /// - `Some((coordinate, total))`: the rounded centroid and the total quantity of the matches.
+        /// - `None`: If no content is found.
+        /// - `Err`: under the same conditions as `scan`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if let Some((coordinate, total)) = scanner.scan_centroid(world, robot, &Pattern::Area(7), &Content::Coin(0))? {
+        ///     println!("{} coins around {:?}", total, coordinate);
+        /// }
+        /// ```
+        pub fn scan_centroid(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            pattern: &Pattern,
+            content: &Content,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            let results = self.scan_all(world, robot, pattern, content)?;
+            if results.is_empty() {
+                return Ok(None);
+            }
+            // tiles holding a content without quantity all weigh the same
+            let weight = |quantity: usize| quantity.max(1);
+            let total_weight: usize = results.iter().map(|x| weight(x.1)).sum();
+            let mut x_sum = 0;
+            let mut y_sum = 0;
+            for (coordinate, quantity) in results.iter() {
+                x_sum += coordinate.get_col() * weight(*quantity);
+                y_sum += coordinate.get_row() * weight(*quantity);
+            }
+            // round half up: floor(sum / total + 1/2)
+            let x = (2 * x_sum + total_weight) / (2 * total_weight);
+            let y = (2 * y_sum + total_weight) / (2 * total_weight);
+            // clamp to the bounding box of the matches
+            let x_min = results.iter().map(|x| x.0.get_col()).min().unwrap();
+            let x_max = results.iter().map(|x| x.0.get_col()).max().unwrap();
+            let y_min = results.iter().map(|x| x.0.get_row()).min().unwrap();
+            let y_max = results.iter().map(|x| x.0.get_row()).max().unwrap();
+            Ok(Some((
+                MapCoordinate::new(x.clamp(x_min, x_max), y.clamp(y_min, y_max)),
+                results.total(),
+            )))
+        }
+
+        /// Validates the pattern of a scan and checks the cooldown.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::MapUnavailable` if the known map cannot be read, `ToolError::Other`
+        /// if the map is empty, `InvalidSizeError` or the cooldown error of `check_cooldown`.
+        fn prepare_scan(&mut self, world: &World, pattern: &Pattern) -> ScanOutcome<()> {
+            let known = robot_map(world).ok_or(MapUnavailable)?;
+            self.prepare_scan_sized(known.len(), pattern)
+        }
+
+        /// Validates the pattern of a scan in a world of the given size and checks the cooldown.
+        fn prepare_scan_sized(&mut self, world_size: usize, pattern: &Pattern) -> ScanOutcome<()> {
+            // a zero-size map has no tile to scan, whatever the pattern
+            if world_size == 0 {
+                return Err(ToolError::Other("empty map".to_string()));
+            }
+            // check if the given pattern size is valid
+            if !pattern.check_size(world_size) {
+                return Err(InvalidSizeError {
+                    size: pattern.size(),
+                    world_size,
+                });
+            }
+            #[cfg(feature = "logging")]
+            log::debug!(
+                "validated {:?}: up to {} tiles in a world of size {}",
+                pattern,
+                pattern.max_tiles(self.include_center),
+                world_size
+            );
+            // check if the scanner is still cooling down
+            self.check_cooldown()
+        }
+
+        /// Returns the coordinates and quantities of the discovered tiles holding the given content.
+        fn matching_tiles(
+            hashmap: HashMap<(usize, usize), Option<Tile>>,
+            content: &Content,
+        ) -> Vec<(MapCoordinate, usize)> {
+            // the origin is dropped together with the rest of the result
+            Self::matching_results(hashmap, content, MapCoordinate::new(0, 0), None)
+                .into_iter()
+                .map(|result| (result.coordinate, result.quantity))
+                .collect()
+        }
+
+        /// Returns a `ScanResult` for each discovered tile holding the given content, scanned by
+        /// a robot at `origin`.
+        ///
+        /// The elevation delta of the results is computed against `robot_elevation`, if known.
+        fn matching_results(
+            mut hashmap: HashMap<(usize, usize), Option<Tile>>,
+            content: &Content,
+            origin: MapCoordinate,
+            robot_elevation: Option<usize>,
+        ) -> Vec<ScanResult> {
+            // retain only the tiles containing the requested content
+            // (tiles left unknown by the discovery never match)
+            hashmap.retain(|_key, val| {
+                val.as_ref().map_or(false, |tile| {
+                    mem::discriminant(&tile.content) == mem::discriminant(content)
+                })
+            });
+            // create a vector containing tile coordinates and corresponding content quantity
+            let mut results: Vec<ScanResult> = Vec::new();
+            for (key, val) in hashmap.iter() {
+                let tile = val.as_ref().unwrap();
+                results.push(Self::scan_result(*key, tile, origin, robot_elevation));
+            }
+            results
+        }
+
+        /// Selects the tile holding the highest quantity of the content, in a single pass over the
+        /// discovered tiles.
+        ///
+        /// The selection key is unique per coordinate, so the result is the same whether the
+        /// tiles are visited sequentially or in parallel.
+        fn best_result(
+            hashmap: &HashMap<(usize, usize), Option<Tile>>,
+            content: &Content,
+            origin: MapCoordinate,
+            robot_elevation: Option<usize>,
+        ) -> Option<ScanResult> {
+            #[cfg(feature = "rayon")]
+            let tiles = hashmap.par_iter();
+            #[cfg(not(feature = "rayon"))]
+            let tiles = hashmap.iter();
+            tiles
+                .filter_map(|(key, tile)| Some((*key, tile.as_ref()?)))
+                .filter(|(_, tile)| mem::discriminant(&tile.content) == mem::discriminant(content))
+                .map(|(key, tile)| Self::scan_result(key, tile, origin, robot_elevation))
+                .max_by_key(|x| selection_key(&x.coordinate, x.quantity))
+        }
+
+        /// Keeps the coordinates not known to the robot yet, in their order.
+        ///
+        /// The coordinates are filtered in place, with the `rayon` feature too: a parallel filter
+        /// would collect the kept ones into a new buffer.
+        fn retain_unknown(known: &[Vec<Option<Tile>>], coordinates: &mut Vec<MapCoordinate>) {
+            coordinates.retain(|coordinate| known[coordinate.row()][coordinate.col()].is_none());
+        }
+
+        /// Describes a matching tile, keyed by `(x, y)`, as a `ScanResult`.
+        fn scan_result(
+            key: (usize, usize),
+            tile: &Tile,
+            origin: MapCoordinate,
+            robot_elevation: Option<usize>,
+        ) -> ScanResult {
+            ScanResult {
+                coordinate: MapCoordinate::from(key),
+                quantity: ResourceScanner::content_quantity(&tile.content),
+                elevation: tile.elevation,
+                elevation_delta: robot_elevation.map(|elevation| tile.elevation as isize - elevation as isize),
+                walkable: tile.tile_type.properties().walk(),
+                origin,
+            }
+        }
+
+        /// Checks whether no known tile on the line between `from` and `to` is strictly higher than
+        /// both of them.
+        ///
+        /// The line is walked with Bresenham's algorithm, excluding its endpoints. Unknown tiles
+        /// never block the line of sight.
+        ///
+        /// # Arguments
+        ///
+        /// * `known` - The map of the tiles known to the robot.
+        /// * `from` - The first endpoint and its elevation.
+        /// * `to` - The second endpoint and its elevation.
+        fn line_of_sight(
+            known: &[Vec<Option<Tile>>],
+            from: (MapCoordinate, usize),
+            to: (MapCoordinate, usize),
+        ) -> bool {
+            let max_elevation = from.1.max(to.1);
+            let (mut x, mut y) = (from.0.get_col() as i64, from.0.get_row() as i64);
+            let (x_end, y_end) = (to.0.get_col() as i64, to.0.get_row() as i64);
+            let dx = (x_end - x).abs();
+            let dy = -(y_end - y).abs();
+            let x_step = if x < x_end { 1 } else { -1 };
+            let y_step = if y < y_end { 1 } else { -1 };
+            let mut error = dx + dy;
+            loop {
+                if x == x_end && y == y_end {
+                    return true;
+                }
+                let double_error = 2 * error;
+                if double_error >= dy {
+                    error += dy;
+                    x += x_step;
+                }
+                if double_error <= dx {
+                    error += dx;
+                    y += y_step;
+                }
+                if x == x_end && y == y_end {
+                    return true;
+                }
+                // unknown tiles are treated as not blocking
+                let tile = known
+                    .get(y as usize)
+                    .and_then(|row| row.get(x as usize))
+                    .and_then(|tile| tile.as_ref());
+                if let Some(tile) = tile {
+                    if tile.elevation > max_elevation {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        /// Returns the quantity of a content, used to compare the matching tiles.
+        ///
+        /// Contents holding a value (e.g. `Coin(usize)`) return it, while contents holding a range
+        /// (`Bin`, `Crate` and `Bank`) return their capacity, computed as `range.end - range.start`.
+        /// Contents holding neither return 0, `Content::None` included: empty tiles are matched
+        /// and listed like any other content, with quantity 0, instead of being dropped.
+        pub(crate) fn content_quantity(content: &Content) -> usize {
+            match content.get_value() {
+                (Some(value), _) => value,
+                (None, Some(range)) => range.len(),
+                (None, None) => 0,
+            }
+        }
+
+        /// Returns the elevation of the tile the robot stands on, or `None` if that tile is not
+        /// known to the robot.
+        fn robot_elevation(world: &World, robot: &impl Runnable) -> Option<usize> {
+            let known = robot_map(world)?;
+            let tile = known
+                .get(robot.get_coordinate().get_row())?
+                .get(robot.get_coordinate().get_col())?
+                .as_ref()?;
+            Some(tile.elevation)
+        }
+
+        /// Scans an explicit list of coordinates for the required content.
+        ///
+        /// The same rules as [`ResourceScanner::scan`] apply: tiles already known to the robot are
+        /// skipped, the energy needed to discover the remaining tiles is checked beforehand and only
+        /// the tiles containing the requested content are returned.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `coordinates`: The absolute coordinates of the tiles to scan.
+        /// - `content`: The content to be searched for.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing either:
+        /// - A vector of `(coordinate, count)` pairs, in the order of `coordinates`, for every newly
+        ///   discovered tile containing the content.
+        /// - `Err(ToolError::OutOfBounds)`: If any of the coordinates lies outside the world, listing
+        ///   all the offending coordinates.
+        /// - `Err`: If the robot doesn't have enough energy to discover the tiles, or if their cost
+        ///   exceeds the cap of [`ResourceScanner::with_max_energy`].
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let suspicious = [MapCoordinate::new(4, 7), MapCoordinate::new(5, 9)];
+        /// let fires = scanner.scan_coordinates(world, robot, &suspicious, &Content::Fire)?;
+        /// ```
+        pub fn scan_coordinates(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            coordinates: &[MapCoordinate],
+            content: &Content,
+        ) -> ScanOutcome<Vec<(MapCoordinate, usize)>> {
+            // check if every coordinate lies inside the world
+            let world_size = robot_map(world).map_or(0, |map| map.len());
+            let out_of_bounds: Vec<MapCoordinate> = coordinates
+                .iter()
+                .filter(|c| c.get_col() >= world_size || c.get_row() >= world_size)
+                .cloned()
+                .collect();
+            if !out_of_bounds.is_empty() {
+                return Err(OutOfBounds(out_of_bounds));
+            }
+            // check if the scanner is still cooling down
+            self.check_cooldown()?;
+
+            // skip the tiles already known and check the energy needed for the rest
+            let sanitized_coordinates = ResourceScanner::get_sanitized_tiles(world, coordinates);
+            #[cfg(feature = "logging")]
+            log::debug!(
+                "scanning {} coordinates: {} known skipped, estimated energy {} of {}",
+                coordinates.len(),
+                coordinates.len() - sanitized_coordinates.len(),
+                self.energy_per_tile() * sanitized_coordinates.len(),
+                robot.get_energy().get_energy_level()
+            );
+            self.check_energy_cap(sanitized_coordinates.len())?;
+            let available = robot.get_energy().get_energy_level();
+            let required = self.energy_per_tile() * sanitized_coordinates.len();
+            if available < required {
+                return Err(NotEnoughEnergy { required, available });
+            }
+
+            let hashmap = self.discover_recorded(world, robot, &sanitized_coordinates)?;
+
+            // keep the tiles containing the requested content, in the given order
+            let mut out = Vec::new();
+            for coordinate in sanitized_coordinates.iter() {
+                let key = coordinate.as_col_row();
+                if let Some(Some(tile)) = hashmap.get(&key) {
+                    if mem::discriminant(&tile.content) == mem::discriminant(content) {
+                        out.push((*coordinate, ResourceScanner::content_quantity(&tile.content)));
+                    }
+                }
+            }
+            Ok(out)
+        }
+
+        /// Scans an explicit list of coordinates, e.g. a footprint computed by the caller, and
+        /// returns the best tile containing the required content.
+        ///
+        /// Unlike [`ResourceScanner::scan_coordinates`], the coordinates outside the world are
+        /// silently dropped. The remaining ones are scanned with the same rules, and the best tile is
+        /// chosen as in [`ResourceScanner::scan`].
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `coordinates`: The absolute coordinates of the tiles to scan.
+        /// - `content`: The content to be searched for.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing either:
+        /// - `Some((coordinates, count))`: The coordinates and count of the best newly discovered
+        ///   tile containing the content.
+        /// - `None`: If no such tile is found.
+        /// - `Err`: If the scanner is cooling down or the robot doesn't have enough energy to
+        ///   discover the tiles.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let ring = [MapCoordinate::new(4, 7), MapCoordinate::new(5, 9), MapCoordinate::new(6, 7)];
+        /// let best = scanner.scan_coordinates_best(world, robot, &ring, &Content::Coin(0))?;
+        /// ```
+        pub fn scan_coordinates_best(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            coordinates: &[MapCoordinate],
+            content: &Content,
+        ) -> ScanOutcome<Option<(MapCoordinate, usize)>> {
+            // drop the coordinates outside the world
+            let world_size = robot_map(world).map_or(0, |map| map.len());
+            let in_bounds: Vec<MapCoordinate> = coordinates
+                .iter()
+                .filter(|c| c.get_col() < world_size && c.get_row() < world_size)
+                .cloned()
+                .collect();
+            let found = self.scan_coordinates(world, robot, &in_bounds, content)?;
+            Ok(found.into_iter().max_by_key(|x| selection_key(&x.0, x.1)))
+        }
+
+        /// Returns the tile at the given coordinate.
+        ///
+        /// If the tile is already known to the robot it is read from the robot map for free,
+        /// which still resets the age of its sighting, see [`ResourceScanner::stale_entries`],
+        /// otherwise only that tile is discovered, costing the energy of a single tile, see
+        /// [`ResourceScanner::with_energy_per_tile`].
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `coordinate`: The absolute coordinate of the tile.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing either:
+        /// - `Some(tile)`: The tile at the given coordinate.
+        /// - `None`: If the tile could not be discovered.
+        /// - `Err(ToolError::OutOfBounds)`: If the coordinate lies outside the world.
+        /// - `Err`: If the robot doesn't have enough energy to discover the tile, or if its cost
+        ///   exceeds the cap of [`ResourceScanner::with_max_energy`].
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// let tile = scanner.scan_tile(world, robot, MapCoordinate::new(4, 7))?;
+        /// ```
+        pub fn scan_tile(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            coordinate: MapCoordinate,
+        ) -> ScanOutcome<Option<Tile>> {
+            let known_coordinates = robot_map(world).unwrap_or_default();
+            let world_size = known_coordinates.len();
+            if coordinate.get_col() >= world_size || coordinate.get_row() >= world_size {
+                return Err(OutOfBounds(vec![coordinate]));
+            }
+            // the tile is already known, no need to discover it, but reading it refreshes the sighting
+            if let Some(tile) = &known_coordinates[coordinate.row()][coordinate.col()] {
+                self.record_sighting(coordinate, &tile.content);
+                return Ok(Some(tile.clone()));
+            }
+            // check if the scanner is still cooling down
+            self.check_cooldown()?;
+            self.check_energy_cap(1)?;
+
+            let hashmap = self.discover_recorded(world, robot, &[coordinate])?;
+            Ok(hashmap
+                .get(&coordinate.as_col_row())
+                .cloned()
+                .flatten())
+        }
+
+        /// Measures a connected deposit: starting from `seed`, follows the adjacent tiles holding
+        /// the required content and returns the whole cluster.
+        ///
+        /// The tiles are expanded breadth first with 4-connectivity (up, right, down, left). Tiles
+        /// already known to the robot are read from the robot map for free, the others are
+        /// discovered one by one, each for the energy per tile of the scanner. At most `max_tiles`
+        /// tiles are inspected, so the energy cost never exceeds `max_tiles` times the energy per
+        /// tile (`DEFAULT_ENERGY_PER_TILE * max_tiles` by default), and the cluster may be partial
+        /// when the cap is reached.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A mutable reference to the world where the robot operates.
+        /// - `robot`: A mutable reference to the robot.
+        /// - `seed`: The absolute coordinate where the expansion starts, usually a scan result.
+        /// - `content`: The content to be followed.
+        /// - `max_tiles`: The maximum number of tiles to inspect.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `Result` containing either:
+        /// - The `(coordinate, count)` pairs of the cluster, in expansion order, empty if the seed
+        ///   does not hold the content.
+        /// - `Err(ToolError::OutOfBounds)`: If the seed lies outside the world.
+        /// - `Err(ToolError::EnergyCapExceeded)`: If discovering `max_tiles` tiles would exceed the
+        ///   cap of [`ResourceScanner::with_max_energy`].
+        /// - `Err`: If the scanner is cooling down or the robot runs out of energy while
+        ///   discovering the tiles.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if let Some((seed, _)) = scanner.scan(world, robot, &Pattern::Area(5), &Content::Coin(0))? {
+        ///     let deposit = scanner.scan_flood(world, robot, seed, &Content::Coin(0), 30)?;
+        ///     println!("{} coins in the deposit", deposit.iter().map(|x| x.1).sum::<usize>());
+        /// }
+        /// ```
+        pub fn scan_flood(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            seed: MapCoordinate,
+            content: &Content,
+            max_tiles: usize,
+        ) -> ScanOutcome<Vec<(MapCoordinate, usize)>> {
+            // the map is read once, the visited set keeps the discovered tiles from being read again
+            let known = robot_map(world).unwrap_or_default();
+            let world_size = known.len();
+            if seed.get_col() >= world_size || seed.get_row() >= world_size {
+                return Err(OutOfBounds(vec![seed]));
+            }
+            // check if the scanner is still cooling down
+            self.check_cooldown()?;
+            // each inspected tile may have to be discovered
+            self.check_energy_cap(max_tiles)?;
+
+            let mut cluster = Vec::new();
+            let mut visited = HashSet::from([seed.as_col_row()]);
+            let mut queue = VecDeque::from([seed]);
+            let mut inspected = 0;
+            while let Some(coordinate) = queue.pop_front() {
+                if inspected == max_tiles {
+                    break;
+                }
+                inspected += 1;
+                // read the tile from the robot map if known, otherwise discover it
+                let tile = match &known[coordinate.row()][coordinate.col()] {
+                    Some(tile) => {
+                        self.record_sighting(coordinate, &tile.content);
+                        Some(tile.clone())
+                    }
+                    None => self
+                        .discover_recorded(world, robot, &[coordinate])?
+                        .remove(&coordinate.as_col_row())
+                        .flatten(),
+                };
+                // the expansion stops at the tiles without the content
+                let Some(tile) = tile else { continue };
+                if mem::discriminant(&tile.content) != mem::discriminant(content) {
+                    continue;
+                }
+                cluster.push((coordinate, ResourceScanner::content_quantity(&tile.content)));
+                for direction in [Direction::Up, Direction::Right, Direction::Down, Direction::Left] {
+                    if let Some(next) = coordinate.step(direction) {
+                        let inside = next.get_col() < world_size && next.get_row() < world_size;
+                        if inside && visited.insert(next.as_col_row()) {
+                            queue.push_back(next);
+                        }
+                    }
+                }
+            }
+            self.record_scan();
+            Ok(cluster)
+        }
+
+        /// Computes the tiles covered by the pattern around the robot without discovering them.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A reference to the world where the robot operates.
+        /// - `robot`: A reference to the robot.
+        /// - `pattern`: The pattern defining the area to be planned.
+        ///
+        /// # Returns
+        ///
+        /// Returns a `ScanPlan` holding the raw footprint of the pattern and the sanitized subset that
+        /// a scan would actually discover. Out-of-bounds coordinates are omitted from both, and both
+        /// are empty if the size of the pattern is invalid, where a scan would fail with
+        /// `InvalidSizeError`, or if the map of the robot is unavailable, where a scan would fail
+        /// with `MapUnavailable`.
+        ///
+        /// The order of the coordinates is guaranteed to be the generation order of the pattern,
+        /// and `sanitized` preserves the relative order of `footprint`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let scanner = ResourceScanner::new();
+        /// let plan = scanner.plan(world, robot, &Pattern::Area(5));
+        /// println!("{} of {} tiles still to discover", plan.sanitized.len(), plan.footprint.len());
+        /// ```
+        pub fn plan(&self, world: &World, robot: &impl Runnable, pattern: &Pattern) -> ScanPlan {
+            let footprint =
+                self.get_target_coordinates(robot, world, pattern)
+                    .unwrap_or_default();
+            let sanitized = ResourceScanner::get_sanitized_tiles(world, &footprint);
+            ScanPlan {
+                footprint,
+                sanitized,
+            }
+        }
+
+        /// Returns the coordinates the pattern covers around `position` in a world of the given
+        /// size, with the boundary policy, the bounds and the center setting of the scanner, in
+        /// the generation order of the pattern.
+        ///
+        /// The coordinates can be of any type implementing [`Coordinatelike`], `MapCoordinate`
+        /// included. The result is empty if the size of the pattern is invalid.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let scanner = ResourceScanner::new();
+        /// let coordinates = scanner.pattern_coordinates(MapCoordinate::new(0, 0), 10, &Pattern::DirectionRight(2));
+        /// assert_eq!(coordinates, vec![MapCoordinate::new(1, 0), MapCoordinate::new(2, 0)]);
+        /// ```
+        pub fn pattern_coordinates<C: Coordinatelike>(&self, position: C, world_size: usize, pattern: &Pattern) -> Vec<C> {
+            self.target_coordinates_at(position, world_size, pattern)
+                .unwrap_or_default()
+        }
+
+        /// Checks, without spending energy, whether scanning the pattern around the robot would
+        /// discover anything, so that a planner can skip the patterns that would be wasted.
+        ///
+        /// Neither the energy of the robot nor the cooldown of the scanner are checked.
+        ///
+        /// # Arguments
+        ///
+        /// - `world`: A reference to the world where the robot operates.
+        /// - `robot`: A reference to the robot.
+        /// - `pattern`: The pattern to check.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::InvalidSizeError` if the size of the pattern is not valid in the
+        /// world, and `ToolError::EmptyCoordinates` if every in-bounds tile of its footprint is
+        /// already known to the robot.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if scanner.can_scan(world, robot, &Pattern::Area(5)).is_ok() {
+        ///     let result = scanner.scan(world, robot, &Pattern::Area(5), &Content::Coin(0))?;
+        /// }
+        /// ```
+        pub fn can_scan(&self, world: &World, robot: &impl Runnable, pattern: &Pattern) -> ScanOutcome<()> {
+            let world_size = robot_map(world).map_or(0, |map| map.len());
+            if !pattern.check_size(world_size) {
+                return Err(InvalidSizeError {
+                    size: pattern.size(),
+                    world_size,
+                });
+            }
+            if self.plan(world, robot, pattern).sanitized.is_empty() {
+                return Err(EmptyCoordinates);
+            }
+            Ok(())
+        }
+
+        /// Combines the results of two scans, e.g. of the two halves of an area, returning the one
+        /// with the higher quantity.
+        ///
+        /// If only one side holds a result, it is returned. Ties are broken as in
+        /// [`ResourceScanner::scan`].
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// let left = Some((MapCoordinate::new(1, 4), 3));
+        /// let right = Some((MapCoordinate::new(8, 4), 5));
+        /// assert_eq!(ResourceScanner::best_of(left, right), right);
+        /// assert_eq!(ResourceScanner::best_of(left, None), left);
+        /// ```
+        pub fn best_of(
+            a: Option<(MapCoordinate, usize)>,
+            b: Option<(MapCoordinate, usize)>,
+        ) -> Option<(MapCoordinate, usize)> {
+            match (a, b) {
+                (Some(a), Some(b)) => {
+                    if selection_key(&b.0, b.1) > selection_key(&a.0, a.1) {
+                        Some(b)
+                    } else {
+                        Some(a)
+                    }
+                }
+                (a, None) => a,
+                (None, b) => b,
+            }
+        }
+
+        /// Compares the matches of two scans of the same region, e.g. to follow fires spreading or
+        /// contents being consumed, without touching the world.
+        ///
+        /// # Arguments
+        ///
+        /// - `previous`: The matches of the earlier scan, as returned by `scan_all`.
+        /// - `current`: The matches of the later scan.
+        ///
         /// # Returns
-        /// Returns `true` if the size is valid, `false` otherwise
-        fn check_size(&self) -> bool {
-            return match self {
-                Pattern::Area(size) if size % 2 == 0 || (*size as i32) < 3 => false,
-                Pattern::DirectionUp(size) if (*size as i32) < 1 => false,
-                Pattern::DirectionRight(size) if (*size as i32) < 1 => false,
-                Pattern::DirectionLeft(size) if (*size as i32) < 1 => false,
-                Pattern::DirectionDown(size) if (*size as i32) < 1 => false,
-                Pattern::DiagonalUpperLeft(size) if (*size as i32) < 1 => false,
-                Pattern::DiagonalUpperRight(size) if (*size as i32) < 1 => false,
-                Pattern::DiagonalLowerLeft(size) if (*size as i32) < 1 => false,
-                Pattern::DiagonalLowerRight(size) if (*size as i32) < 1 => false,
-                Pattern::StraightStar(size) if (*size as i32) < 1 => false,
-                Pattern::DiagonalStar(size) if (*size as i32) < 1 => false,
-                _ => true,
-            };
+        ///
+        /// Returns a [`ScanDiff`] with the matches that appeared, disappeared or changed quantity.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::coordinates::map_coordinate::MapCoordinate;
+        /// use resource_scanner_tool::tool::resource_scanner::ResourceScanner;
+        /// let previous = [(MapCoordinate::new(1, 1), 2), (MapCoordinate::new(3, 1), 5)];
+        /// let current = [(MapCoordinate::new(1, 1), 4)];
+        /// let diff = ResourceScanner::diff(&previous, &current);
+        /// assert_eq!(diff.changed, vec![(MapCoordinate::new(1, 1), 2, 4)]);
+        /// assert_eq!(diff.removed, vec![(MapCoordinate::new(3, 1), 5)]);
+        /// assert!(diff.added.is_empty());
+        /// ```
+        pub fn diff(previous: &[(MapCoordinate, usize)], current: &[(MapCoordinate, usize)]) -> ScanDiff {
+            let before: HashMap<MapCoordinate, usize> = previous.iter().copied().collect();
+            let after: HashMap<MapCoordinate, usize> = current.iter().copied().collect();
+            let mut diff = ScanDiff::default();
+            for &(coordinate, quantity) in current {
+                match before.get(&coordinate) {
+                    None => diff.added.push((coordinate, quantity)),
+                    Some(&old) if old != quantity => diff.changed.push((coordinate, old, quantity)),
+                    Some(_) => {}
+                }
+            }
+            diff.removed = previous
+                .iter()
+                .filter(|(coordinate, _)| !after.contains_key(coordinate))
+                .copied()
+                .collect();
+            diff
         }
-    }
-
-    pub struct ResourceScanner {}
-
-    impl Tools for ResourceScanner {}
-
-    impl ResourceScanner {
-        /// The scan function scans an area around the robot for the required content according to the pattern.
 
+        /// Suggests the next move of the robot toward `target`, e.g. to call `go` once per tick.
+        ///
+        /// The direction follows the axis with the larger delta, preferring the horizontal one
+        /// when both deltas are equal, as in [`MapCoordinate::direction_to`].
+        ///
         /// # Arguments
         ///
-        /// - `world`: A mutable reference to the world where the robot operates.
-        /// - `robot`: A mutable reference to the robot.
-        /// - `pattern`: The pattern defining the area to be scanned.
-        /// - `content`: The content to be searched for in the area.
+        /// - `robot`: A reference to the robot.
+        /// - `target`: The coordinate to reach, e.g. the one returned by a scan.
         ///
-        /// ## Notes on Content Behavior
+        /// # Returns
         ///
-        /// The `Content` enum can have different associated types, the scan tool is designed to operate seamlessly for `usize` and `()`.
-        /// The contents `Content::Bin(Range<usize>)`, `Content::Crate(Range<usize>)` and `Content::Bank(Range<usize>)` are currently not supported.
+        /// Returns the direction to feed to `go`, or `None` if the robot is already on the target.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if let Ok(Some((coordinate, _))) = scanner.scan(world, robot, &Pattern::Area(5), &Content::Coin(0)) {
+        ///     if let Some(direction) = scanner.direction_to(robot, coordinate) {
+        ///         let _ = go(robot, world, direction);
+        ///     }
+        /// }
+        /// ```
+        pub fn direction_to(&self, robot: &impl Runnable, target: MapCoordinate) -> Option<Direction> {
+            let position = MapCoordinate::new(
+                robot.get_coordinate().get_col(),
+                robot.get_coordinate().get_row(),
+            );
+            position.direction_to(&target)
+        }
+
+        /// Suggests a sequence of moves leading the robot to `target`.
+        ///
+        /// The path is L-shaped: all the horizontal moves (`Left` or `Right`) come first, followed
+        /// by all the vertical moves (`Up` or `Down`). Its length is the Manhattan distance between
+        /// the robot and the target.
+        ///
+        /// This is only a hint, not a pathfinder: walkability and energy costs of the tiles along
+        /// the way are not checked.
+        ///
+        /// # Arguments
+        ///
+        /// - `robot`: A reference to the robot.
+        /// - `target`: The coordinate to reach, e.g. the one returned by a scan.
         ///
         /// # Returns
         ///
-        /// Returns a `Result` containing either:
-        /// - `Some((coordinates, count))`: If content is found, where `coordinates` is the location and `count` is the number of occurrences.
-        /// - `None`: If no content is found.
-        /// - `Err`: If the robot doesn't have enough energy to perform the scan.
+        /// Returns the directions to feed to `go`, one per move. The vector is empty if the robot is
+        /// already on the target.
         ///
+        /// # Examples
         ///
-        /// # Energy Cost
+        /// ```ignore
+        /// use resource_scanner_tool::tool::resource_scanner::{Pattern, ResourceScanner};
+        /// let mut scanner = ResourceScanner::new();
+        /// if let Ok(Some((coordinate, _))) = scanner.scan(world, robot, &Pattern::Area(5), &Content::Coin(0)) {
+        ///     for direction in scanner.path_to(robot, coordinate) {
+        ///         let _ = go(robot, world, direction);
+        ///     }
+        /// }
+        /// ```
+        pub fn path_to(&self, robot: &impl Runnable, target: MapCoordinate) -> Vec<Direction> {
+            let x_robot = robot.get_coordinate().get_col();
+            let y_robot = robot.get_coordinate().get_row();
+            let mut path = Vec::new();
+            // horizontal moves first
+            let horizontal = if target.get_col() > x_robot {
+                Direction::Right
+            } else {
+                Direction::Left
+            };
+            for _ in 0..target.get_col().abs_diff(x_robot) {
+                path.push(horizontal.clone());
+            }
+            // then vertical moves
+            let vertical = if target.get_row() > y_robot {
+                Direction::Down
+            } else {
+                Direction::Up
+            };
+            for _ in 0..target.get_row().abs_diff(y_robot) {
+                path.push(vertical.clone());
+            }
+            path
+        }
+
+        /// Discovers the tiles covered by the pattern which are not yet known to the robot.
         ///
-        /// This tool uses the underlying interface `discover_tile` to discover tiles. Since it uses
-        /// 3 energy for each discovered tile, the scan function first checks if enough energy is present
-        /// to complete the task.
-        /// The following are the different energy costs based on pattern and size (assuming no tiles
-        /// have already been discovered):
-        ///
-        /// - `Area(size)`: free if size = 3, else 12 * (size - 1)
-        /// - `DirectionUp(size)`: 3 * size
-        /// - `DirectionRight(size)`: 3 * size
-        /// - `DirectionLeft(size)`: 3 * size
-        /// - `DirectionDown(size)`: 3 * size
-        /// - `DiagonalUpperLeft(size)`: 3 * size
-        /// - `DiagonalUpperRight(size)`: 3 * size
-        /// - `DiagonalLowerLeft(size)`: 3 * size
-        /// - `DiagonalLowerRight(size)`: 3 * size
-        /// - `StraightStar(size)`: 12 * size
-        /// - `DiagonalStar(size)`: 12 * size
+        /// # Arguments
         ///
-        pub fn scan(
+        /// * `world` - A mutable reference to the `World` in which the tiles are discovered.
+        /// * `robot` - A mutable reference to an object implementing the `Runnable` trait.
+        /// * `pattern` - A reference to the `Pattern` that defines the tiles to discover.
+        ///
+        /// # Returns
+        ///
+        /// Returns the discovered tiles, keyed by `(x, y)` coordinates, together with the
+        /// `ScanReport` describing the discovery.
+        ///
+        /// # Errors
+        ///
+        /// Returns a `ToolError` if the underlying interfaces fail.
+        fn discover(
             &mut self,
             world: &mut World,
             robot: &mut impl Runnable,
-            pattern: Pattern,
-            content: Content,
-        ) -> Result<Option<(MapCoordinate, usize)>, Box<dyn Error>> {
-            // check if the given content is supported
-            match content {
-                Content::Bin(_)|Content::Bank(_)|Content::Crate(_) => return Err(Box::new(ContentNotSupported)),
-                _ => ()
-            }
-            // check if the given pattern size is valid
-            if !pattern.check_size() {
-                return Err(Box::new(InvalidSizeError));
-            }
-            // check whether using robot_view is more convenient
-            let use_robot_view;
-            match pattern {
-                Pattern::Area(3) => use_robot_view = true,
-                _ => use_robot_view = false,
-            }
+            pattern: &Pattern,
+        ) -> ScanOutcome<(HashMap<(usize, usize), Option<Tile>>, ScanReport)> {
+            let max_energy = self.max_energy;
+            self.with_robot_backend(world, robot, |scanner, backend| {
+                scanner.discover_backend(backend, pattern, max_energy, false, None)
+            })
+        }
+
+        /// Runs `f` with a backend discovering through the robot, lending it the buffer of the
+        /// `(row, col)` keys of the scanner, which is reused across the discoveries.
+        fn with_robot_backend<R: Runnable, T>(
+            &mut self,
+            world: &mut World,
+            robot: &mut R,
+            f: impl FnOnce(&mut Self, &mut RobotBackend<'_, R>) -> T,
+        ) -> T {
+            let mut backend = RobotBackend::new(world, robot, mem::take(&mut self.row_col_keys));
+            let out = f(self, &mut backend);
+            self.row_col_keys = backend.into_keys();
+            out
+        }
+
+        /// Discovers the tiles covered by the pattern which are not yet known to the robot,
+        /// through the given backend. See [`ResourceScanner::discover`].
+        ///
+        /// If discovering them would cost more than `max_energy`, the discovery fails with
+        /// `ToolError::EnergyCapExceeded`, or, if `trim` is set, only the unknown tiles nearest to
+        /// the robot that fit within the cap are discovered.
+        ///
+        /// If a budget is given, the estimated cost is reserved from it before the discovery, and
+        /// the part not spent is refunded afterwards.
+        fn discover_backend(
+            &mut self,
+            backend: &mut impl DiscoveryBackend,
+            pattern: &Pattern,
+            max_energy: Option<usize>,
+            trim: bool,
+            budget: Option<&EnergyBudget>,
+        ) -> ScanOutcome<(HashMap<(usize, usize), Option<Tile>>, ScanReport)> {
+            let known = backend.known_map().ok_or(MapUnavailable)?;
+            self.discover_known(backend, &known, pattern, max_energy, trim, budget)
+        }
 
-            // get coordinates of tiles to scan
-            let sanitized_coordinates =
-                ResourceScanner::get_sanitized_tiles(robot, world, &pattern);
+        /// Performs the discovery of [`ResourceScanner::discover_backend`] with the map already
+        /// read from the backend, so that a scan reads it once.
+        fn discover_known(
+            &mut self,
+            backend: &mut impl DiscoveryBackend,
+            known: &[Vec<Option<Tile>>],
+            pattern: &Pattern,
+            max_energy: Option<usize>,
+            trim: bool,
+            budget: Option<&EnergyBudget>,
+        ) -> ScanOutcome<(HashMap<(usize, usize), Option<Tile>>, ScanReport)> {
+            let (coordinates, report) = self.plan_discovery(&*backend, known, pattern, max_energy, trim, budget)?;
+            let energy_before = backend.energy_level();
 
             // discover the tiles
-            let mut tiles;
-            if use_robot_view {
-                // closure converting robot_view output to discover_tiles output
-                let to_hashmap = |tilemap: Vec<Vec<Option<Tile>>>| ->  Result<HashMap<(usize, usize), Option<Tile>>, LibError> {
-                    let mut hashmap = HashMap::new();
-                    let x_robot = robot.get_coordinate().get_col();
-                    let y_robot = robot.get_coordinate().get_row();
-                    for (y_area, tile_vec) in tilemap.iter().enumerate() {
-                        for (x_area, tile) in tile_vec.iter().enumerate() {
-                            match tile {
-                                Some(t) => {
-                                    let x = x_robot + x_area - 1;
-                                    let y = y_robot + y_area - 1;
-                                    hashmap.insert((x, y),Some(t.to_owned()))
-                                },
-                                None => None
-                            };
-                        }
-                    }
-                    return Ok(hashmap)
-                };
-                tiles = to_hashmap(robot_view(robot, world))
+            let mut tiles = HashMap::new();
+            let outcome = if report.used_robot_view {
+                backend.view(&coordinates, &mut tiles)
             } else {
-                let binding: Vec<(usize, usize)> = sanitized_coordinates
-                    .iter()
-                    .map(|x| (x.get_height(), x.get_width()))
-                    .collect();
-                // switch the input coordinates since the discover_tiles interface is takes (y,x) tuple
-                tiles = discover_tiles(robot, world, &binding);
-                // switch the output coordinates
-                match tiles {
-                    Ok(ref mut hashmap) => {
-                        let mut to_insert = Vec::new();
-                        // Collect items for insertion and removal
-                        for (key, value) in hashmap.iter_mut() {
-                            let new_key = (key.1, key.0);
-                            to_insert.push((new_key, value.clone()));
-                        }
+                backend.discover(&coordinates, &mut tiles)
+            };
+            let tiles = outcome.map(|()| tiles);
+            let requested = coordinates.len();
+            self.coordinate_buffer = coordinates;
+            self.finish_discovery(requested, tiles, energy_before, backend.energy_level(), budget, report)
+        }
 
-                        // Remove old keys
-                        for key in hashmap.keys().cloned().collect::<Vec<_>>() {
-                            hashmap.remove(&key);
-                        }
+        /// Computes the coordinates a discovery through the backend has to discover, checking
+        /// the cap and reserving the estimated cost from the budget, see
+        /// [`ResourceScanner::discover_backend`].
+        ///
+        /// The coordinates are returned in the buffer of the scanner, to be given back once they
+        /// are discovered.
+        fn plan_discovery(
+            &mut self,
+            backend: &impl DiscoveryBackend,
+            known: &[Vec<Option<Tile>>],
+            pattern: &Pattern,
+            max_energy: Option<usize>,
+            trim: bool,
+            budget: Option<&EnergyBudget>,
+        ) -> ScanOutcome<(Vec<MapCoordinate>, ScanReport)> {
+            let mut report = ScanReport::default();
+            let use_robot_view = self.uses_robot_view(pattern);
+            report.used_robot_view = use_robot_view;
 
-                        // Insert new keys
-                        for item in to_insert.iter() {
-                            hashmap.insert(item.0, item.1.clone());
-                        }
-                    }
-                    Err(error) => {
-                        return match error {
-                            LibError::NotEnoughEnergy => Err(Box::new(ToolError::NotEnoughEnergy)),
-                            LibError::NoMoreDiscovery => Err(Box::new(ToolError::NoMoreDiscovery)),
-                            other => Err(Box::new(ToolError::Other(format!("{:?}", other)))),
-                        }
-                    }
+            // get coordinates of tiles to scan, in the buffer reused across scans
+            self.check_strict_bounds(backend.position(), known.len(), pattern)?;
+            let mut sanitized_coordinates = mem::take(&mut self.coordinate_buffer);
+            self.last_clipped_count =
+                self.fill_target_coordinates(backend.position(), known.len(), pattern, &mut sanitized_coordinates);
+            let target_count = sanitized_coordinates.len();
+            ResourceScanner::retain_unknown(known, &mut sanitized_coordinates);
+            report.tiles_requested = target_count;
+            report.tiles_known = target_count - sanitized_coordinates.len();
+            // check the estimated cost against the cap, the known tiles are free
+            let energy_per_tile = if use_robot_view { 0 } else { self.energy_per_tile() };
+            let estimated = energy_per_tile * sanitized_coordinates.len();
+            if let Some(cap) = max_energy.filter(|&cap| estimated > cap) {
+                if !trim {
+                    self.coordinate_buffer = sanitized_coordinates;
+                    return Err(EnergyCapExceeded { estimated, cap });
                 }
+                // keep the tiles nearest to the robot
+                let position = backend.position();
+                sanitized_coordinates.sort_by_key(|coordinate| {
+                    (coordinate.manhattan_distance(&position), coordinate.get_row(), coordinate.get_col())
+                });
+                sanitized_coordinates.truncate(cap / energy_per_tile);
             }
-
-            return match tiles {
-                Ok(ref mut hashmap) => {
-                    // retain only the tiles containing the requested content
-                    hashmap.retain(|_key, val| {
-                        mem::discriminant(&val.as_ref().unwrap().content)
-                            == mem::discriminant(&content)
+            // reserve the cost of the tiles left to discover
+            let reserved = energy_per_tile * sanitized_coordinates.len();
+            report.energy_estimated = reserved;
+            if let Some(budget) = budget {
+                if !budget.try_reserve(reserved) {
+                    self.coordinate_buffer = sanitized_coordinates;
+                    return Err(BudgetExhausted {
+                        estimated: reserved,
+                        remaining: budget.remaining(),
                     });
-                    // if the hashmap is empty, return None
-                    if hashmap.is_empty() {
-                        return Ok(None);
-                    }
-                    // create a vector containing tile coordinates and corresponding content quantity
-                    let mut tile_vec: Vec<(MapCoordinate, usize)> = Vec::new();
-                    for (key, val) in hashmap.iter() {
-                        tile_vec.push((
-                            MapCoordinate::from(*key),
-                            val.as_ref().unwrap().content.get_value().0.unwrap(),
-                        ));
-                    }
-                    // find the tile coordinate corresponding to the max value
-                    let result = tile_vec.iter().max_by_key(|x| x.1).cloned().unwrap();
-                    // return the result
-                    Ok(Some(result))
                 }
-                Err(error) => {
-                    return match error {
-                        LibError::NotEnoughEnergy => Err(Box::new(ToolError::NotEnoughEnergy)),
-                        LibError::NoMoreDiscovery => Err(Box::new(ToolError::NoMoreDiscovery)),
-                        other => Err(Box::new(ToolError::Other(format!("{:?}", other)))),
+            }
+            #[cfg(feature = "logging")]
+            log::debug!(
+                "scanning {:?} via {}: {} tiles in the footprint, {} known skipped, {} to discover, estimated energy {} of {}",
+                pattern,
+                if use_robot_view { "robot_view" } else { "discover_tiles" },
+                target_count,
+                report.tiles_known,
+                sanitized_coordinates.len(),
+                reserved,
+                backend.energy_level()
+            );
+            Ok((sanitized_coordinates, report))
+        }
+
+        /// Completes the report of a discovery of `requested` tiles, given the discovered tiles and
+        /// the energy level before and after discovering them, recording the sightings and the
+        /// consumed energy and refunding the budget.
+        ///
+        /// Every discovery of the scanner ends here, so that the cache and the energy counter
+        /// see all of them, and a successful discovery starts the cooldown. A scan discovering
+        /// its tiles one at a time is thus recorded as soon as it has spent energy, even if a
+        /// later tile fails.
+        fn finish_discovery(
+            &mut self,
+            requested: usize,
+            tiles: Result<HashMap<(usize, usize), Option<Tile>>, LibError>,
+            energy_before: usize,
+            energy_after: usize,
+            budget: Option<&EnergyBudget>,
+            mut report: ScanReport,
+        ) -> ScanOutcome<(HashMap<(usize, usize), Option<Tile>>, ScanReport)> {
+            report.energy_consumed = energy_before.saturating_sub(energy_after);
+            if let Ok(hashmap) = &tiles {
+                for (key, tile) in hashmap.iter() {
+                    if let Some(tile) = tile {
+                        report.discovered.push(DiscoveredTile {
+                            coordinate: MapCoordinate::from(*key),
+                            tile_type: tile.tile_type,
+                            content: tile.content.clone(),
+                            elevation: tile.elevation,
+                        });
                     }
                 }
+                report
+                    .discovered
+                    .sort_by_key(|tile| (tile.coordinate.get_row(), tile.coordinate.get_col()));
+                report.tiles_discovered = report.discovered.len();
+                // remember the discovered contents
+                for tile in report.discovered.iter() {
+                    self.record_sighting(tile.coordinate, &tile.content);
+                }
+            }
+            self.total_energy_consumed += report.energy_consumed;
+            // refund the energy which was not spent
+            if let Some(budget) = budget {
+                budget.refund(report.energy_estimated.saturating_sub(report.energy_consumed));
+            }
+
+            return match tiles {
+                Ok(hashmap) => {
+                    self.record_scan();
+                    Ok((hashmap, report))
+                }
+                Err(error) => Err(self.to_tool_error(error, requested, energy_before)),
             };
         }
 
-        /// Computes and returns a vector of target coordinates based on the given pattern.
+        /// Discovers the given coordinates through the robot, outside of a pattern, e.g. one
+        /// tile at a time, recording them like the pattern scans do.
+        fn discover_recorded(
+            &mut self,
+            world: &mut World,
+            robot: &mut impl Runnable,
+            coordinates: &[MapCoordinate],
+        ) -> ScanOutcome<HashMap<(usize, usize), Option<Tile>>> {
+            let report = ScanReport {
+                tiles_requested: coordinates.len(),
+                ..ScanReport::default()
+            };
+            self.with_robot_backend(world, robot, |scanner, backend| {
+                let energy_before = backend.energy_level();
+                let mut tiles = HashMap::new();
+                let outcome = backend.discover(coordinates, &mut tiles);
+                let energy_after = backend.energy_level();
+                let (hashmap, _) =
+                    scanner.finish_discovery(coordinates.len(), outcome.map(|()| tiles), energy_before, energy_after, None, report)?;
+                Ok(hashmap)
+            })
+        }
+
+        /// Fails if the scanner has strict bounds and a tile of the pattern, scanned from
+        /// `origin`, falls outside the world.
+        fn check_strict_bounds(&self, origin: MapCoordinate, world_size: usize, pattern: &Pattern) -> ScanOutcome<()> {
+            if !self.strict_bounds || self.boundary_policy != BoundaryPolicy::Clip {
+                return Ok(());
+            }
+            let Some(offsets) = self.offset_table(pattern) else {
+                return Ok(());
+            };
+            if offsets
+                .iter()
+                .any(|&(dx, dy)| origin.try_offset(dx, dy, (world_size, world_size)).is_none())
+            {
+                return Err(Other("pattern out of bounds".to_string()));
+            }
+            Ok(())
+        }
+
+        /// Checks whether the pattern is served through `robot_view`, which is free, instead of
+        /// `discover_tiles`.
+        fn uses_robot_view(&self, pattern: &Pattern) -> bool {
+            // (robot_view does not wrap around the world edges, and can be disabled)
+            match pattern {
+                Pattern::Area(3) => self.boundary_policy == BoundaryPolicy::Clip && !self.force_discover_tiles,
+                _ => false,
+            }
+        }
+
+        /// Discovers the given coordinates through the `discover_tiles` interface.
         ///
         /// # Arguments
         ///
+        /// * `world` - A mutable reference to the `World` in which the tiles are discovered.
         /// * `robot` - A mutable reference to an object implementing the `Runnable` trait.
+        /// * `keys` - The buffer of the `(row, col)` keys handed to `discover_tiles`.
+        /// * `coordinates` - The coordinates to discover.
+        /// * `tiles` - The map the output of `discover_tiles` is moved into, keyed by `(x, y)`
+        ///   coordinates.
+        pub(crate) fn discover_coordinates(
+            world: &mut World,
+            robot: &mut impl Runnable,
+            keys: &mut Vec<(usize, usize)>,
+            coordinates: &[MapCoordinate],
+            tiles: &mut HashMap<(usize, usize), Option<Tile>>,
+        ) -> Result<(), LibError> {
+            // switch the input coordinates since the discover_tiles interface takes (y,x) tuples,
+            // written into the buffer reused across the discoveries
+            keys.clear();
+            keys.extend(coordinates.iter().map(|coordinate| coordinate.as_row_col()));
+            let discovered = discover_tiles(robot, world, keys)?;
+            // switch the output coordinates, moving the tiles into the map of the caller
+            tiles.extend(discovered.into_iter().map(|((row, col), tile)| ((col, row), tile)));
+            Ok(())
+        }
+
+        /// Converts an error of the underlying interfaces, raised discovering `requested` tiles
+        /// with `available` energy, into a `ToolError`, adding the context of the discovery to
+        /// the energy and discovery errors. The other errors are converted as is.
+        fn to_tool_error(&self, error: LibError, requested: usize, available: usize) -> ToolError {
+            match ToolError::from(error) {
+                NotEnoughEnergy { .. } => NotEnoughEnergy {
+                    required: self.energy_per_tile() * requested,
+                    available,
+                },
+                NoMoreDiscovery { .. } => NoMoreDiscovery { requested },
+                other => other,
+            }
+        }
+        /// Computes and returns a vector of target coordinates based on the given pattern.
+        ///
+        /// # Arguments
+        ///
+        /// * `robot` - A reference to an object implementing the `Runnable` trait.
         /// * `world` - A reference to the `World` in which the coordinates are computed.
         /// * `pattern` - A reference to the `Pattern` that defines the coordinate computation.
         ///
+        /// The coordinates falling outside the world are handled according to the boundary policy
+        /// of the scanner, the ones outside its bounds are omitted, and the robot tile is part of
+        /// the directional, diagonal and star patterns only if the scanner includes the center.
+        ///
         /// # Returns
         ///
         /// Returns an `Option<Vec<map_coordinate>>` representing the vector of target coordinates.
-        /// Returns `None` if no valid coordinates are found.
+        /// Returns `None` if no valid coordinates are found. With `BoundaryPolicy::Wrap` each
+        /// coordinate appears once, even when the pattern overlaps itself.
         ///
         /// # Examples
         ///
@@ -296,277 +4709,135 @@ pub mod resource_scanner {
         /// println!("{:?}", coordinates);
         /// ```
         fn get_target_coordinates(
-            robot: &mut impl Runnable,
+            &self,
+            robot: &impl Runnable,
             world: &World,
             pattern: &Pattern,
         ) -> Option<Vec<MapCoordinate>> {
-            let mut out = Vec::new();
-            let world_size = robot_map(world).unwrap().len();
-            let (y_robot, x_robot) = (
-                robot.get_coordinate().get_row(),
+            let position = MapCoordinate::new(
                 robot.get_coordinate().get_col(),
+                robot.get_coordinate().get_row(),
             );
+            // an unavailable map is a world of size 0, which no pattern fits
+            self.target_coordinates_at(position, robot_map(world).map_or(0, |map| map.len()), pattern)
+        }
 
-            // according to the pattern, compute the corresponding tile coordinates
-            match pattern {
-                Pattern::Area(size) => {
-                    let length = *size as i32;
-                    let x_area_robot = length / 2;
-                    let y_area_robot = length / 2;
-                    for x in 0..length {
-                        for y in 0..length {
-                            // compute the tile coordinates in the world FoR (Frame of Reference) from the tile coordinates in the area FoR
-                            let x_world = (x_robot as i32) + x - x_area_robot;
-                            let y_world = (y_robot as i32) + y - y_area_robot;
-                            // check if the coordinates are out of bound, if so omit them
-                            if !(x_world < 0
-                                || x_world > (world_size as i32) - 1
-                                || y_world < 0
-                                || y_world > (world_size as i32) - 1)
-                            {
-                                out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                            }
-                        }
-                    }
-                }
-
-                Pattern::DirectionLeft(size) => {
-                    let length = *size as i32;
-                    let y_world = y_robot as i32;
-                    for index in 0..=length {
-                        let x = -index;
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the robot FoR
-                        let x_world = (x_robot as i32) + x;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
-                }
-
-                Pattern::DirectionRight(size) => {
-                    let length = *size as i32;
-                    let y_world = y_robot as i32;
-                    for x in 0..=length {
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the robot FoR
-                        let x_world = (x_robot as i32) + x;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
-                }
-
-                Pattern::DirectionUp(size) => {
-                    let length = *size as i32;
-                    let x_world = x_robot as i32;
-                    for y in 0..=length {
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the robot FoR
-                        let y_world = (y_robot as i32) - y;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
-                }
-
-                Pattern::DirectionDown(size) => {
-                    let length = *size as i32;
-                    let x_world = x_robot as i32;
-                    for y in 0..=length {
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the robot FoR
-                        let y_world = (y_robot as i32) + y;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
-                }
-
-                Pattern::DiagonalUpperLeft(size) => {
-                    let length = *size as i32;
-                    for i in 0..=length {
-                        let x = -i;
-                        let y = -i;
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the area FoR
-                        let x_world = (x_robot as i32) + x;
-                        let y_world = (y_robot as i32) + y;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
-                }
+        /// Computes the target coordinates of the pattern like
+        /// [`ResourceScanner::get_target_coordinates`], for a robot at `position` in a world of
+        /// the given size.
+        fn target_coordinates_at<C: Coordinatelike>(
+            &self,
+            position: C,
+            world_size: usize,
+            pattern: &Pattern,
+        ) -> Option<Vec<C>> {
+            let mut out = Vec::new();
+            self.fill_target_coordinates(position, world_size, pattern, &mut out);
+            return if out.len() == 0 { None } else { Some(out) };
+        }
 
-                Pattern::DiagonalUpperRight(size) => {
-                    let length = *size as i32;
-                    for i in 0..=length {
-                        let x = i;
-                        let y = -i;
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the area FoR
-                        let x_world = (x_robot as i32) + x;
-                        let y_world = (y_robot as i32) + y;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
-                }
+        /// Computes the target coordinates of the pattern like
+        /// [`ResourceScanner::target_coordinates_at`], writing them into `out`, which is cleared
+        /// first and keeps its capacity.
+        ///
+        /// Returns the number of coordinates clipped by the world edge, not counting the ones
+        /// left out by the bounds or covered twice once wrapped.
+        fn fill_target_coordinates<C: Coordinatelike>(
+            &self,
+            position: C,
+            world_size: usize,
+            pattern: &Pattern,
+            out: &mut Vec<C>,
+        ) -> usize {
+            out.clear();
+            let boundary_policy = self.boundary_policy;
+            // invalid sizes cover no tiles
+            if !pattern.check_size(world_size) {
+                return 0;
+            }
+            let origin = MapCoordinate::new(position.col(), position.row());
+            let Some(offsets) = self.offset_table(pattern) else {
+                return 0;
+            };
 
-                Pattern::DiagonalLowerLeft(size) => {
-                    let length = *size as i32;
-                    for i in 0..=length {
-                        let x = -i;
-                        let y = i;
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the area FoR
-                        let x_world = (x_robot as i32) + x;
-                        let y_world = (y_robot as i32) + y;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
-                }
+            // according to the pattern, compute the corresponding tile coordinates: move them
+            // from the robot FoR (Frame of Reference) to the world FoR, then wrap or omit the out of
+            // bound ones according to the policy (the order is kept in parallel too)
+            let to_world = |&offset: &(i64, i64)| boundary_policy.apply::<C>(origin, offset, world_size);
+            #[cfg(feature = "rayon")]
+            out.par_extend(offsets.par_iter().filter_map(to_world));
+            #[cfg(not(feature = "rayon"))]
+            out.extend(offsets.iter().filter_map(to_world));
+            let clipped = offsets.len() - out.len();
 
-                Pattern::DiagonalLowerRight(size) => {
-                    let length = *size as i32;
-                    for i in 0..=length {
-                        let x = i;
-                        let y = i;
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the area FoR
-                        let x_world = (x_robot as i32) + x;
-                        let y_world = (y_robot as i32) + y;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
-                }
+            // keep only the coordinates inside the bounds, if any
+            if let Some((top_left, bottom_right)) = self.bounds {
+                out.retain(|coordinate| {
+                    (top_left.get_col()..=bottom_right.get_col()).contains(&coordinate.col())
+                        && (top_left.get_row()..=bottom_right.get_row()).contains(&coordinate.row())
+                });
+            }
 
-                Pattern::DiagonalStar(size) => {
-                    let length = *size as i32;
-                    //push robot coordinates
-                    out.push(MapCoordinate::new(x_robot, y_robot));
-                    //push rest of coordinates
-                    for i in 1..=length {
-                        for multiplier in [(1, 1), (1, -1), (-1, 1), (1, 1)] {
-                            let x = multiplier.0 * i;
-                            let y = multiplier.1 * i;
-                            // compute the tile coordinates in the world FoR from the tile coordinates in the area FoR
-                            let x_world = (x_robot as i32) + x;
-                            let y_world = (y_robot as i32) + y;
-                            // check if the coordinates are out of bound, if so omit them
-                            if !(x_world < 0
-                                || x_world > (world_size as i32) - 1
-                                || y_world < 0
-                                || y_world > (world_size as i32) - 1)
-                            {
-                                out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                            }
-                        }
-                    }
-                }
+            // wrapped patterns may overlap themselves, keep the first occurrence of each coordinate
+            if boundary_policy == BoundaryPolicy::Wrap {
+                let mut seen = HashSet::new();
+                out.retain(|coordinate| seen.insert((coordinate.col(), coordinate.row())));
+            }
+            clipped
+        }
 
-                Pattern::StraightStar(size) => {
-                    let length = *size as i32;
-
-                    // horizontal arms
-                    let y_world = y_robot as i32;
-                    for x in -length..=length {
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the robot FoR
-                        let x_world = (x_robot as i32) + x;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
+        /// Returns the offsets of the pattern from the robot, computed once per pattern and then
+        /// reused by the following scans, which only translate and clip them.
+        ///
+        /// At most `OFFSET_TABLE_CAPACITY` patterns are kept: the table is emptied when a new
+        /// pattern would exceed it, so a robot cycling through many sizes does not grow it
+        /// unboundedly. The table sits behind a `Mutex`, so that the scanner stays `Send` and
+        /// `Sync`.
+        pub(crate) fn offset_table(&self, pattern: &Pattern) -> Option<Arc<[(i64, i64)]>> {
+            let key = (pattern.clone(), self.include_center);
+            // a panic while holding the lock cannot leave the table inconsistent
+            let mut tables = self.offset_tables.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(offsets) = tables.get(&key) {
+                return Some(Arc::clone(offsets));
+            }
+            let offsets: Arc<[(i64, i64)]> = pattern.offsets(self.include_center)?.into();
+            if tables.len() >= OFFSET_TABLE_CAPACITY {
+                tables.clear();
+            }
+            tables.insert(key, Arc::clone(&offsets));
+            Some(offsets)
+        }
 
-                    // vertical upper arm
-                    let x_world = x_robot as i32;
-                    for y in 1..=length {
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the robot FoR
-                        let y_world = (y_robot as i32) + y;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
+        /// Returns the address of the coordinate buffer reused by the pattern scans.
+        #[cfg(test)]
+        pub(crate) fn coordinate_buffer_ptr(&self) -> *const MapCoordinate {
+            self.coordinate_buffer.as_ptr()
+        }
 
-                    // vertical lower arm
-                    for y in -length..0 {
-                        // compute the tile coordinates in the world FoR from the tile coordinates in the robot FoR
-                        let y_world = (y_robot as i32) + y;
-                        // check if the coordinates are out of bound, if so omit them
-                        if !(x_world < 0
-                            || x_world > (world_size as i32) - 1
-                            || y_world < 0
-                            || y_world > (world_size as i32) - 1)
-                        {
-                            out.push(MapCoordinate::new(x_world as usize, y_world as usize));
-                        }
-                    }
-                }
-            }
+        /// Returns the address of the buffer of the keys handed to `discover_tiles`.
+        #[cfg(test)]
+        pub(crate) fn row_col_keys_ptr(&self) -> *const (usize, usize) {
+            self.row_col_keys.as_ptr()
+        }
 
-            return if out.len() == 0 { None } else { Some(out) };
+        /// Returns the number of patterns whose offsets are currently kept.
+        #[cfg(test)]
+        pub(crate) fn offset_table_len(&self) -> usize {
+            self.offset_tables.lock().unwrap_or_else(PoisonError::into_inner).len()
         }
 
-        /// Returns a vector of sanitized coordinates to be scanned based on the provided pattern,
-        /// excluding coordinates already known by the robot.
+        /// Returns a vector of sanitized coordinates to be scanned, obtained from the given target
+        /// coordinates by excluding the ones already known by the robot.
         ///
         /// # Arguments
         ///
-        /// * `robot` - A mutable reference to an object implementing the `Runnable` trait.
         /// * `world` - A reference to the `World` in which the coordinates are scanned.
-        /// * `pattern` - A reference to the `Pattern` that defines the scanning coordinates.
+        /// * `target_vector` - The coordinates covered by the pattern, as returned by `get_target_coordinates`.
         ///
         /// # Returns
         ///
-        /// Returns a vector of `map_coordinate` representing the sanitized coordinates.
-        ///
-        /// # Errors
-        ///
-        /// Returns an empty vector if no target coordinates are found.
+        /// Returns a vector of `map_coordinate` representing the sanitized coordinates, in the same
+        /// order as `target_vector`.
         ///
         /// # Examples
         ///
@@ -577,36 +4848,134 @@ pub mod resource_scanner {
         /// let pattern = Pattern::Area(3);
         ///
         /// // Get sanitized coordinates
-        /// let sanitized_coordinates = get_sanitized_tiles(&mut robot, &world, &pattern);
+        /// let target_vector = scanner.get_target_coordinates(&mut robot, &world, &pattern).unwrap_or_default();
+        /// let sanitized_coordinates = get_sanitized_tiles(&world, &target_vector);
         /// println!("{:?}", sanitized_coordinates);
         /// ```
-        fn get_sanitized_tiles(
-            robot: &mut impl Runnable,
-            world: &World,
-            pattern: &Pattern,
+        fn get_sanitized_tiles(world: &World, target_vector: &[MapCoordinate]) -> Vec<MapCoordinate> {
+            ResourceScanner::unknown_coordinates(&robot_map(world).unwrap_or_default(), target_vector)
+        }
+
+        /// Returns the coordinates of `target_vector` not yet known in the given map, like
+        /// [`ResourceScanner::get_sanitized_tiles`].
+        fn unknown_coordinates(
+            known_coordinates: &[Vec<Option<Tile>>],
+            target_vector: &[MapCoordinate],
         ) -> Vec<MapCoordinate> {
-            let target_vector = ResourceScanner::get_target_coordinates(robot, world, pattern);
-
-            return match target_vector {
-                Some(mut v) => {
-                    let mut tiles_to_remove = Vec::new();
-                    let known_coordinates = robot_map(world).unwrap();
-                    for (index, coordinate) in v.iter().enumerate() {
-                        if known_coordinates[coordinate.get_width()][coordinate.get_height()]
-                            .is_some()
-                        {
-                            tiles_to_remove.push(index);
-                        }
-                    }
-                    // sort and then iterate in inverse order
-                    tiles_to_remove.sort();
-                    for index in tiles_to_remove.iter().rev() {
-                        v.remove(*index);
-                    }
-                    v
-                }
-                None => Vec::new(),
-            };
+            target_vector
+                .iter()
+                .filter(|coordinate| {
+                    // coordinates outside the map are not known
+                    let tile = known_coordinates.get(coordinate.row()).and_then(|row| row.get(coordinate.col()));
+                    !matches!(tile, Some(Some(_)))
+                })
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[cfg(feature = "json")]
+    impl ResourceScanner {
+        /// Writes the contents remembered by the scanner, with their age, and its energy counter
+        /// as JSON, so that they can be restored with [`ResourceScanner::load_cache`] after a
+        /// restart.
+        ///
+        /// It is available with the `json` feature.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::Other` if the cache cannot be written.
+        pub fn save_cache(&self, writer: impl std::io::Write) -> ScanOutcome<()> {
+            let tiles: Vec<serde_json::Value> = self
+                .cached_entries()
+                .map(|(coordinate, entry)| {
+                    serde_json::json!({
+                        "col": coordinate.get_col(),
+                        "row": coordinate.get_row(),
+                        "content": content_to_json(&entry.content),
+                        "age": entry.age,
+                    })
+                })
+                .collect();
+            let cache = serde_json::json!({
+                "total_energy_consumed": self.total_energy_consumed,
+                "tiles": tiles,
+            });
+            serde_json::to_writer(writer, &cache).map_err(|error| ToolError::Other(format!("cannot save cache: {}", error)))
+        }
+
+        /// Replaces the contents remembered by the scanner and its energy counter with the ones
+        /// read from JSON written by [`ResourceScanner::save_cache`].
+        ///
+        /// The cache is not checked against the world: loading a cache saved on a different world
+        /// yields wrong hits from [`ResourceScanner::cached_find`].
+        ///
+        /// It is available with the `json` feature.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ToolError::Other` if the input cannot be read or is not a valid cache, in which
+        /// case the scanner is left unchanged.
+        pub fn load_cache(&mut self, reader: impl std::io::Read) -> ScanOutcome<()> {
+            let invalid = |what: &str| ToolError::Other(format!("invalid cache: {}", what));
+            let value: serde_json::Value =
+                serde_json::from_reader(reader).map_err(|error| invalid(&error.to_string()))?;
+            let total_energy_consumed = value["total_energy_consumed"]
+                .as_u64()
+                .ok_or_else(|| invalid("missing total_energy_consumed"))? as usize;
+            let mut cache = HashMap::new();
+            let mut discovered = CoordinateSet::default();
+            for tile in value["tiles"].as_array().ok_or_else(|| invalid("missing tiles"))? {
+                let col = tile["col"].as_u64().ok_or_else(|| invalid("missing col"))? as usize;
+                let row = tile["row"].as_u64().ok_or_else(|| invalid("missing row"))? as usize;
+                let content = content_from_json(&tile["content"]).ok_or_else(|| invalid("unknown content"))?;
+                let age = tile["age"].as_u64().ok_or_else(|| invalid("missing age"))? as usize;
+                cache.insert((col, row), CacheEntry { content, age });
+                discovered.insert(MapCoordinate::new(col, row));
+            }
+            self.cache = cache;
+            self.discovered = discovered;
+            self.total_energy_consumed = total_energy_consumed;
+            Ok(())
+        }
+    }
+
+    /// Encodes a content as a JSON object holding its kind and its value or range.
+    #[cfg(feature = "json")]
+    fn content_to_json(content: &Content) -> serde_json::Value {
+        let kind = format!("{:?}", content);
+        let kind = kind.split('(').next().unwrap_or_default();
+        match content.get_value() {
+            (Some(value), _) => serde_json::json!({ "kind": kind, "value": value }),
+            (None, Some(range)) => serde_json::json!({ "kind": kind, "start": range.start, "end": range.end }),
+            (None, None) => serde_json::json!({ "kind": kind }),
         }
     }
+
+    /// Decodes a content encoded by `content_to_json`, returning `None` if it is malformed.
+    #[cfg(feature = "json")]
+    fn content_from_json(value: &serde_json::Value) -> Option<Content> {
+        let number = |key: &str| value[key].as_u64().map(|n| n as usize);
+        let range = || Some(number("start")?..number("end")?);
+        let content = match value["kind"].as_str()? {
+            "Rock" => Content::Rock(number("value")?),
+            "Tree" => Content::Tree(number("value")?),
+            "Garbage" => Content::Garbage(number("value")?),
+            "Fire" => Content::Fire,
+            "Coin" => Content::Coin(number("value")?),
+            "Bin" => Content::Bin(range()?),
+            "Crate" => Content::Crate(range()?),
+            "Bank" => Content::Bank(range()?),
+            "Water" => Content::Water(number("value")?),
+            "Market" => Content::Market(number("value")?),
+            "Fish" => Content::Fish(number("value")?),
+            "Building" => Content::Building,
+            "Bush" => Content::Bush(number("value")?),
+            "JollyBlock" => Content::JollyBlock(number("value")?),
+            "Scarecrow" => Content::Scarecrow,
+            "None" => Content::None,
+            _ => return None,
+        };
+        Some(content)
+    }
 }
@@ -1,55 +1,428 @@
-pub(crate) mod test_helpers {
-    
-    use robotics_lib::world::tile::Content::Coin;
-    use robotics_lib::world::tile::Tile;
-    
-
-    pub fn print_grid(world_map: &Vec<Vec<Tile>>, known_tiles: &Option<Vec<Vec<Option<Tile>>>>, robot_pos: (usize,usize)) {
-        let x = robot_pos.1;
-        let y = robot_pos.0;
-        println!("printing world map");
-        println!("robot_pos: ({},{})",x, y);
-        for (height,row) in world_map.iter().enumerate() {
-            for (width, tile) in row.iter().enumerate() {
-                if robot_pos == (height, width) {
-                    print!("R");
-                }
-                else if tile.content == Coin(1) {
-                    print!("o");
-                }
-                else {
-                    print!("_");
-                }
-            }
-            println!("");
+/// Helpers to inspect the world while writing or debugging a robot.
+pub mod test_helpers {
+    use crate::coordinates::map_coordinate::MapCoordinate;
+    use robotics_lib::world::tile::{Content, Tile};
+    use std::collections::HashSet;
+
+    /// Returns the character representing the given content in [`grid_to_string`].
+    ///
+    /// | Content      | Char |
+    /// |--------------|------|
+    /// | `Rock`       | `r`  |
+    /// | `Tree`       | `t`  |
+    /// | `Garbage`    | `g`  |
+    /// | `Fire`       | `f`  |
+    /// | `Coin`       | `c`  |
+    /// | `Bin`        | `b`  |
+    /// | `Crate`      | `k`  |
+    /// | `Bank`       | `$`  |
+    /// | `Water`      | `w`  |
+    /// | `Market`     | `m`  |
+    /// | `Fish`       | `i`  |
+    /// | `Building`   | `u`  |
+    /// | `Bush`       | `s`  |
+    /// | `JollyBlock` | `j`  |
+    /// | `Scarecrow`  | `y`  |
+    /// | `None`       | `.`  |
+    pub fn content_char(content: &Content) -> char {
+        match content {
+            Content::Rock(_) => 'r',
+            Content::Tree(_) => 't',
+            Content::Garbage(_) => 'g',
+            Content::Fire => 'f',
+            Content::Coin(_) => 'c',
+            Content::Bin(_) => 'b',
+            Content::Crate(_) => 'k',
+            Content::Bank(_) => '$',
+            Content::Water(_) => 'w',
+            Content::Market(_) => 'm',
+            Content::Fish(_) => 'i',
+            Content::Building => 'u',
+            Content::Bush(_) => 's',
+            Content::JollyBlock(_) => 'j',
+            Content::Scarecrow => 'y',
+            Content::None => '.',
         }
+    }
+
+    /// Renders the world map and the tiles known by the robot as text.
+    ///
+    /// Each tile is rendered with [`content_char`], the robot with `R` and the unknown tiles with
+    /// `_`. Columns are labelled by their last digit on top of each grid, rows by their index on
+    /// the left.
+    ///
+    /// # Arguments
+    ///
+    /// * `world_map` - The whole world, indexed `[row][col]`.
+    /// * `known_tiles` - The tiles known by the robot, as returned by `robot_map`.
+    /// * `robot_pos` - The position of the robot as `(row, col)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::utils::test_helpers::grid_to_string;
+    /// use robotics_lib::world::tile::{Content, Tile, TileType};
+    /// let tile = |content| Tile { tile_type: TileType::Grass, content, elevation: 0 };
+    /// let world = vec![vec![tile(Content::None), tile(Content::Coin(1))]; 2];
+    /// let rendered = grid_to_string(&world, &None, (0, 0));
+    /// assert!(rendered.contains(" 01\n0Rc\n1.c\n"));
+    /// ```
+    pub fn grid_to_string(
+        world_map: &[Vec<Tile>],
+        known_tiles: &Option<Vec<Vec<Option<Tile>>>>,
+        robot_pos: (usize, usize),
+    ) -> String {
+        let mut out = String::new();
+        out.push_str("world map:\n");
+        push_grid(&mut out, world_map, |row, col, tile| {
+            if robot_pos == (row, col) {
+                'R'
+            } else {
+                content_char(&tile.content)
+            }
+        });
 
         match known_tiles {
             Some(vector) => {
-                println!("printing known tiles:");
-                println!("robot_pos: ({},{})",x, y);
-                for (height, row) in vector.iter().enumerate() {
-                    for (width, tile) in row.iter().enumerate() {
-                        match tile {
-                            Some(t) => {
-                                if robot_pos == (height, width) {
-                                    print!("R");
-                                }
-                                else if t.content == Coin(1) {
-                                    print!("o");
-                                }
-                                else {
-                                    print!("x");
-                                }
-                            }
-                            None => print!("_"),
-                        }
+                out.push_str("known tiles:\n");
+                push_grid(&mut out, vector, |row, col, tile| match tile {
+                    _ if robot_pos == (row, col) => 'R',
+                    Some(tile) => content_char(&tile.content),
+                    None => '_',
+                });
+            }
+            None => out.push_str("known tiles is None\n"),
+        }
+        out
+    }
+
+    /// Prints the world map and the tiles known by the robot, as rendered by [`grid_to_string`].
+    pub fn print_grid(
+        world_map: &[Vec<Tile>],
+        known_tiles: &Option<Vec<Vec<Option<Tile>>>>,
+        robot_pos: (usize, usize),
+    ) {
+        print!("{}", grid_to_string(world_map, known_tiles, robot_pos));
+    }
+
+    /// Renders the world map with the footprint of a scan drawn on it, to see which tiles a scan
+    /// covers and why it may miss a resource.
+    ///
+    /// From the top layer down, each tile is rendered as:
+    /// - `R`: the robot.
+    /// - its [`content_char`]: a tile holding some content, inside or outside the footprint.
+    /// - `+`: an empty footprint tile already known to the robot, which a scan does not discover.
+    /// - `*`: an empty footprint tile still unknown to the robot.
+    /// - `.`: any other empty tile.
+    ///
+    /// Rows and columns are labelled as in [`grid_to_string`].
+    ///
+    /// # Arguments
+    ///
+    /// * `world_map` - The whole world, indexed `[row][col]`.
+    /// * `known_tiles` - The tiles known by the robot, as returned by `robot_map`.
+    /// * `robot_pos` - The position of the robot as `(row, col)`.
+    /// * `footprint` - The coordinates covered by the scan, e.g. the footprint of a `ScanPlan`.
+    pub fn render_scan(
+        world_map: &[Vec<Tile>],
+        known_tiles: &Option<Vec<Vec<Option<Tile>>>>,
+        robot_pos: (usize, usize),
+        footprint: &[MapCoordinate],
+    ) -> String {
+        let footprint: HashSet<(usize, usize)> = footprint.iter().map(|c| c.as_row_col()).collect();
+        let is_known = |row: usize, col: usize| {
+            known_tiles
+                .as_ref()
+                .and_then(|known| known.get(row)?.get(col)?.as_ref())
+                .is_some()
+        };
+        let mut out = String::new();
+        push_grid(&mut out, world_map, |row, col, tile| match tile {
+            _ if robot_pos == (row, col) => 'R',
+            tile if tile.content != Content::None => content_char(&tile.content),
+            _ if footprint.contains(&(row, col)) && is_known(row, col) => '+',
+            _ if footprint.contains(&(row, col)) => '*',
+            _ => '.',
+        });
+        out
+    }
+
+    /// Appends a grid with labelled rows and columns to `out`, rendering each cell with `cell`,
+    /// which receives the row, the column and the cell itself.
+    fn push_grid<T>(out: &mut String, rows: &[Vec<T>], cell: impl Fn(usize, usize, &T) -> char) {
+        let label_width = rows.len().saturating_sub(1).to_string().len();
+        let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        // column labels
+        out.push_str(&" ".repeat(label_width));
+        for col in 0..columns {
+            out.push_str(&(col % 10).to_string());
+        }
+        out.push('\n');
+        for (row_index, row) in rows.iter().enumerate() {
+            out.push_str(&format!("{:>width$}", row_index, width = label_width));
+            for (col_index, tile) in row.iter().enumerate() {
+                out.push(cell(row_index, col_index, tile));
+            }
+            out.push('\n');
+        }
+    }
+}
+
+/// Statistics and queries about the part of the world already charted by the robot, all free
+/// of energy.
+pub mod map_stats {
+    use crate::coordinates::map_coordinate::MapCoordinate;
+    use crate::tool::resource_scanner::ResourceScanner;
+    use robotics_lib::interface::robot_map;
+    use robotics_lib::runner::Runnable;
+    use robotics_lib::world::tile::{Content, Tile};
+    use robotics_lib::world::World;
+    use std::collections::HashMap;
+    use std::mem;
+
+    /// The `MapStats` struct summarizes the tiles known to the robot.
+    ///
+    /// ## Fields
+    ///
+    /// - `total_tiles`: The number of tiles of the world.
+    /// - `known_tiles`: The number of those tiles known to the robot.
+    /// - `coverage`: The fraction of the world known to the robot, from 0.0 to 1.0.
+    /// - `contents`: The total quantity of each content on the known tiles, keyed by the content
+    ///   with a zero value (e.g. `Content::Coin(0)`). Contents not seen are absent, while empty
+    ///   tiles, which are walkable drop spots, are counted under `Content::None` with quantity 0.
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct MapStats {
+        pub total_tiles: usize,
+        pub known_tiles: usize,
+        pub coverage: f64,
+        pub contents: HashMap<Content, usize>,
+    }
+
+    impl MapStats {
+        /// Computes the statistics of the given known map, indexed `[row][col]` as returned by
+        /// `robot_map`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use resource_scanner_tool::utils::map_stats::MapStats;
+        /// use robotics_lib::world::tile::{Content, Tile, TileType};
+        /// let tile = Tile { tile_type: TileType::Grass, content: Content::Coin(4), elevation: 0 };
+        /// let stats = MapStats::from_known_map(&[vec![Some(tile), None], vec![None, None]]);
+        /// assert_eq!(stats.coverage, 0.25);
+        /// assert_eq!(stats.contents.get(&Content::Coin(0)), Some(&4));
+        /// ```
+        pub fn from_known_map(known_map: &[Vec<Option<Tile>>]) -> Self {
+            let mut stats = MapStats::default();
+            for tile in known_map.iter().flatten() {
+                stats.total_tiles += 1;
+                let Some(tile) = tile else { continue };
+                stats.known_tiles += 1;
+                *stats.contents.entry(content_kind(&tile.content)).or_insert(0) +=
+                    ResourceScanner::content_quantity(&tile.content);
+            }
+            if stats.total_tiles > 0 {
+                stats.coverage = stats.known_tiles as f64 / stats.total_tiles as f64;
+            }
+            stats
+        }
+    }
+
+    /// Returns the statistics of the tiles known to the robot, see [`MapStats`].
+    ///
+    /// When the known map is not available every statistic is zero.
+    pub fn map_stats(world: &World) -> MapStats {
+        robot_map(world).map_or_else(MapStats::default, |known| MapStats::from_known_map(&known))
+    }
+
+    /// Searches the whole known map for the tile holding the given content nearest to the robot,
+    /// without discovering any tile.
+    ///
+    /// The content is matched by variant, ignoring its value. The nearest tile is the one at the
+    /// lowest Manhattan distance, then with the lowest row and the lowest column; the tile under
+    /// the robot is at distance 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - A reference to the world where the robot operates.
+    /// * `robot` - A reference to the robot.
+    /// * `content` - The content to be searched for.
+    ///
+    /// # Returns
+    ///
+    /// Returns the coordinates and count of the nearest known tile holding the content, or `None`
+    /// if no known tile holds it.
+    pub fn find_nearest_known(
+        world: &World,
+        robot: &impl Runnable,
+        content: &Content,
+    ) -> Option<(MapCoordinate, usize)> {
+        let known = robot_map(world)?;
+        let position = MapCoordinate::new(
+            robot.get_coordinate().get_col(),
+            robot.get_coordinate().get_row(),
+        );
+        let mut candidates = Vec::new();
+        for (row, tiles) in known.iter().enumerate() {
+            for (col, tile) in tiles.iter().enumerate() {
+                match tile {
+                    Some(tile) if mem::discriminant(&tile.content) == mem::discriminant(content) => {
+                        let quantity = ResourceScanner::content_quantity(&tile.content);
+                        candidates.push((MapCoordinate::new(col, row), quantity));
                     }
-                    println!("");
+                    _ => {}
                 }
             }
+        }
+        candidates.into_iter().min_by_key(|(coordinate, _)| {
+            (
+                coordinate.manhattan_distance(&position),
+                coordinate.get_row(),
+                coordinate.get_col(),
+            )
+        })
+    }
+
+    /// Returns the density of the given content over the known map, see
+    /// [`heatmap_from_known_map`]. When the known map is not available the heatmap is empty.
+    pub fn density_heatmap(world: &World, content: &Content, block_size: usize) -> Vec<Vec<Option<usize>>> {
+        robot_map(world)
+            .map(|known| heatmap_from_known_map(&known, content, block_size))
+            .unwrap_or_default()
+    }
+
+    /// Divides the known map in square blocks of `block_size` tiles per side and sums the
+    /// quantity of the given content held by the known tiles of each block.
+    ///
+    /// The content is matched by variant, ignoring its value. The blocks on the right and bottom
+    /// edges are smaller when the map size is not a multiple of `block_size`.
+    ///
+    /// # Arguments
+    ///
+    /// * `known_map` - The tiles known to the robot, indexed `[row][col]` as returned by `robot_map`.
+    /// * `content` - The content to be counted.
+    /// * `block_size` - The side of the blocks.
+    ///
+    /// # Returns
+    ///
+    /// Returns the heatmap indexed `[block row][block column]`, where the blocks with no known
+    /// tile are `None`. The heatmap is empty if `block_size` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::utils::map_stats::heatmap_from_known_map;
+    /// use robotics_lib::world::tile::{Content, Tile, TileType};
+    /// let tile = Tile { tile_type: TileType::Grass, content: Content::Coin(4), elevation: 0 };
+    /// let mut known = vec![vec![None; 4]; 4];
+    /// known[0][0] = Some(tile);
+    /// let heatmap = heatmap_from_known_map(&known, &Content::Coin(0), 2);
+    /// assert_eq!(heatmap, vec![vec![Some(4), None], vec![None, None]]);
+    /// ```
+    pub fn heatmap_from_known_map(
+        known_map: &[Vec<Option<Tile>>],
+        content: &Content,
+        block_size: usize,
+    ) -> Vec<Vec<Option<usize>>> {
+        if block_size == 0 {
+            return Vec::new();
+        }
+        let blocks = |len: usize| (len + block_size - 1) / block_size;
+        let columns = known_map.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut heatmap = vec![vec![None; blocks(columns)]; blocks(known_map.len())];
+        if columns == 0 {
+            return heatmap;
+        }
+        let corner = MapCoordinate::new(columns - 1, known_map.len() - 1);
+        for coordinate in MapCoordinate::iter_rect(MapCoordinate::new(0, 0), corner) {
+            let (row, col) = coordinate.as_row_col();
+            let Some(Some(tile)) = known_map[row].get(col) else { continue };
+            let block = heatmap[row / block_size][col / block_size].get_or_insert(0);
+            if mem::discriminant(&tile.content) == mem::discriminant(content) {
+                *block += ResourceScanner::content_quantity(&tile.content);
+            }
+        }
+        heatmap
+    }
+
+    /// Renders a heatmap as text, one character per block.
+    ///
+    /// Unknown blocks are rendered with `_` and empty blocks with `.`, while the other blocks
+    /// are rendered with one of `:-=+*#%@`, scaled so that the densest block is `@`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use resource_scanner_tool::utils::map_stats::heatmap_to_string;
+    /// let heatmap = vec![vec![Some(8), Some(1)], vec![Some(0), None]];
+    /// assert_eq!(heatmap_to_string(&heatmap), "@:\n._\n");
+    /// ```
+    pub fn heatmap_to_string(heatmap: &[Vec<Option<usize>>]) -> String {
+        const INTENSITY: [char; 9] = ['.', ':', '-', '=', '+', '*', '#', '%', '@'];
+        let max = heatmap.iter().flatten().flatten().copied().max().unwrap_or(0);
+        let mut out = String::new();
+        for row in heatmap {
+            for block in row {
+                out.push(match block {
+                    None => '_',
+                    Some(0) => INTENSITY[0],
+                    // scale 1..=max to the 8 non-empty intensities, rounding up
+                    Some(value) => INTENSITY[(value * 8 + max - 1) / max],
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Exports the tiles known to the robot as a JSON array, e.g. for a visualizer running in
+    /// another process.
+    ///
+    /// Each known tile is an object with the `col`, `row`, `tile_type`, `content` and
+    /// `elevation` keys, where the tile type and the content are strings written as by their
+    /// `Debug` implementation, e.g. `"Grass"` and `"Coin(2)"`. Unknown tiles are omitted, and
+    /// the array is empty when the known map is not available.
+    ///
+    /// It is available with the `json` feature.
+    #[cfg(feature = "json")]
+    pub fn export_known_map_json(world: &World) -> String {
+        let known = robot_map(world).unwrap_or_default();
+        let mut tiles = Vec::new();
+        for (row, row_tiles) in known.iter().enumerate() {
+            for (col, tile) in row_tiles.iter().enumerate() {
+                if let Some(tile) = tile {
+                    tiles.push(serde_json::json!({
+                        "col": col,
+                        "row": row,
+                        "tile_type": format!("{:?}", tile.tile_type),
+                        "content": format!("{:?}", tile.content),
+                        "elevation": tile.elevation,
+                    }));
+                }
+            }
+        }
+        serde_json::Value::Array(tiles).to_string()
+    }
 
-            None => println!("known tiles is None"),
+    /// Returns the content of the same variant as `content`, holding a zero value.
+    fn content_kind(content: &Content) -> Content {
+        match content {
+            Content::Rock(_) => Content::Rock(0),
+            Content::Tree(_) => Content::Tree(0),
+            Content::Garbage(_) => Content::Garbage(0),
+            Content::Fire => Content::Fire,
+            Content::Coin(_) => Content::Coin(0),
+            Content::Bin(_) => Content::Bin(0..0),
+            Content::Crate(_) => Content::Crate(0..0),
+            Content::Bank(_) => Content::Bank(0..0),
+            Content::Water(_) => Content::Water(0),
+            Content::Market(_) => Content::Market(0),
+            Content::Fish(_) => Content::Fish(0),
+            Content::Building => Content::Building,
+            Content::Bush(_) => Content::Bush(0),
+            Content::JollyBlock(_) => Content::JollyBlock(0),
+            Content::Scarecrow => Content::Scarecrow,
+            Content::None => Content::None,
         }
     }
-}
\ No newline at end of file
+}